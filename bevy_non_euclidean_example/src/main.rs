@@ -6,13 +6,33 @@ use bevy_asset::prelude::*;
 use bevy_core::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_input::prelude::*;
+use bevy_log::prelude::*;
 use bevy_math::prelude::*;
 use bevy_pbr_spherical::prelude::*;
+use bevy_reflect::{Reflect, TypeUuid};
 use bevy_render_spherical::prelude::*;
+use bevy_scene_spherical::prelude::*;
 use bevy_transform_spherical::prelude::*;
 
+/// Where F5 saves the level, relative to the `assets` folder.
+/// Pass this path as an argument to load the saved level instead of building it.
+const LEVEL_PATH: &str = "scenes/tetrahedron.scn.ron";
+
+// Fixed ids, so that saved levels find the same assets when they are loaded.
+const CUBE_MESH: HandleUntyped =
+    HandleUntyped::weak_from_u64(Mesh::TYPE_UUID, 0x1d5a_53c0_8e2b_7f41);
+const CUBE_MATERIAL: HandleUntyped =
+    HandleUntyped::weak_from_u64(StandardMaterial::TYPE_UUID, 0x6b0e_91f7_24c8_3a5d);
+
+/// The space the example takes place in. Swap in `Elliptic` to identify antipodal points.
+type Space = Spherical;
+
+/// The path of a saved level to load, from the command line.
+struct Level(Option<String>);
+
 fn main() {
     App::build()
+        .insert_resource(Level(std::env::args().nth(1)))
         .insert_resource(Msaa { samples: 4 })
         .add_plugin(bevy_log::LogPlugin::default())
         .add_plugin(bevy_core::CorePlugin::default())
@@ -20,18 +40,23 @@ fn main() {
         .add_plugin(bevy_input::InputPlugin::default())
         .add_plugin(bevy_window::WindowPlugin::default())
         .add_plugin(bevy_asset::AssetPlugin::default())
-        .add_plugin(bevy_render_spherical::RenderPlugin::default())
-        .add_plugin(bevy_pbr_spherical::PbrPlugin::default())
+        .add_plugin(bevy_render_spherical::RenderPlugin::<Space>::default())
+        .add_plugin(bevy_pbr_spherical::PbrPlugin::<Space>::default())
+        .add_plugin(bevy_scene_spherical::ScenePlugin::default())
         .add_plugin(bevy_gilrs::GilrsPlugin::default())
         .add_plugin(bevy_winit::WinitPlugin::default())
         .add_plugin(bevy_wgpu::WgpuPlugin::default())
+        .register_type::<Camera>()
         .add_startup_system(setup.system())
         .add_system(motion.system())
         .add_system(rotation.system())
+        .add_system(save.exclusive_system())
         .add_system(bevy_input::system::exit_on_esc_system.system())
         .run();
 }
 
+#[derive(Default, Reflect)]
+#[reflect(Component)]
 struct Camera;
 
 fn motion(
@@ -73,16 +98,55 @@ fn rotation(
     }
 }
 
+/// Saves the level when F5 is pressed.
+fn save(world: &mut World) {
+    if !world
+        .get_resource::<Input<KeyCode>>()
+        .unwrap()
+        .just_pressed(KeyCode::F5)
+    {
+        return;
+    }
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("assets")
+        .join(LEVEL_PATH);
+    let result = save_scene_ron(world)
+        .map_err(|error| error.to_string())
+        .and_then(|scene| {
+            std::fs::create_dir_all(path.parent().unwrap())
+                .and_then(|()| std::fs::write(&path, scene))
+                .map_err(|error| error.to_string())
+        });
+    match result {
+        Ok(()) => info!("Saved the level to {}", path.display()),
+        Err(error) => error!("Could not save the level: {}", error),
+    }
+}
+
 fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+    mut scene_spawner: ResMut<SceneSpawner>,
+    level: Res<Level>,
 ) {
-    let cube_handle = meshes.add(Mesh::from(shape::Cube { size: 0.1 }));
-    let cube_material_handle = materials.add(StandardMaterial {
-        base_color: Color::rgb(0.8, 0.7, 0.6),
-        ..Default::default()
-    });
+    meshes.set_untracked(CUBE_MESH, Mesh::from(shape::Cube { size: 0.1 }));
+    materials.set_untracked(
+        CUBE_MATERIAL,
+        StandardMaterial {
+            base_color: Color::rgb(0.8, 0.7, 0.6),
+            ..Default::default()
+        },
+    );
+
+    if let Some(path) = &level.0 {
+        scene_spawner.spawn_dynamic(asset_server.load(path.as_str()));
+        return;
+    }
+
+    let cube_handle: Handle<Mesh> = CUBE_MESH.typed();
+    let cube_material_handle: Handle<StandardMaterial> = CUBE_MATERIAL.typed();
 
     // cubes
     for transform in [
@@ -4,9 +4,11 @@ pub mod render_graph;
 
 mod entity;
 mod light;
+mod quotient;
 
 pub use entity::*;
 pub use light::*;
+pub use quotient::*;
 
 pub mod prelude {
     pub use crate::{entity::*, light::Light, StandardMaterial};
@@ -14,15 +16,24 @@ pub mod prelude {
 
 use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, Assets, Handle};
-use bevy_ecs::system::IntoSystem;
-use bevy_render_spherical::{prelude::Color, shader};
+use bevy_ecs::{schedule::ParallelSystemDescriptorCoercion, system::IntoSystem};
+use bevy_render_spherical::{prelude::Color, shader, space::RenderGeometry, RenderSystem};
+use bevy_transform_spherical::{space::Spherical, InterpolationSystem, TransformSystem};
 use render_graph::add_pbr_graph;
+use std::marker::PhantomData;
 
 /// NOTE: this isn't PBR yet. consider this name "aspirational" :)
-#[derive(Default)]
-pub struct PbrPlugin;
+///
+/// Lights the space `G`, which must match the `RenderPlugin`.
+pub struct PbrPlugin<G: RenderGeometry = Spherical>(PhantomData<G>);
 
-impl Plugin for PbrPlugin {
+impl<G: RenderGeometry> Default for PbrPlugin<G> {
+    fn default() -> Self {
+        PbrPlugin(PhantomData)
+    }
+}
+
+impl<G: RenderGeometry> Plugin for PbrPlugin<G> {
     fn build(&self, app: &mut AppBuilder) {
         app.add_asset::<StandardMaterial>()
             .register_type::<Light>()
@@ -30,8 +41,20 @@ impl Plugin for PbrPlugin {
                 CoreStage::PostUpdate,
                 shader::asset_shader_defs_system::<StandardMaterial>.system(),
             )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                quotient::quotient_image_spawn_system.system(),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                quotient::quotient_image_update_system
+                    .system()
+                    .after(TransformSystem::TransformPropagate)
+                    .after(InterpolationSystem::TransformInterpolate)
+                    .before(RenderSystem::VisibleEntities),
+            )
             .init_resource::<AmbientLight>();
-        add_pbr_graph(app.world_mut());
+        add_pbr_graph::<G>(app.world_mut());
 
         // add default StandardMaterial
         let mut materials = app
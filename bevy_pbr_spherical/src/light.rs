@@ -1,6 +1,6 @@
 use bevy_core::Byteable;
 use bevy_render_spherical::camera::{CameraProjection, PerspectiveProjection};
-use bevy_transform_spherical::components::GlobalTransform;
+use bevy_transform_spherical::{space::Geometry, universe::UniverseRadius};
 
 pub use bevy_pbr::{AmbientLight, Light};
 
@@ -15,30 +15,31 @@ pub(crate) struct LightRaw {
 unsafe impl Byteable for LightRaw {}
 
 impl LightRaw {
-    pub fn from(light: &Light, global_transform: &GlobalTransform) -> LightRaw {
+    /// The light's `depth` and `range` are in world units of a universe with the given radius.
+    pub fn from<G: Geometry>(
+        light: &Light,
+        global_transform: &G::GlobalTransform,
+        radius: UniverseRadius,
+    ) -> LightRaw {
         let perspective = PerspectiveProjection {
             fov: light.fov,
             aspect_ratio: 1.0,
-            tan_near: light
-                .depth
-                .start
+            tan_near: (light.depth.start / radius.0)
                 .clamp(0.00001, std::f32::consts::PI - 0.00001)
                 .tan(),
-            tan_far: light
-                .depth
-                .end
+            tan_far: (light.depth.end / radius.0)
                 .clamp(0.00001, std::f32::consts::PI - 0.00001)
                 .tan(),
         };
 
-        let proj = perspective.get_projection_matrix() * global_transform.compute_matrix();
+        let proj = perspective.get_projection_matrix() * G::compute_matrix(global_transform);
 
         // premultiply color by intensity
         // we don't use the alpha at all, so no reason to multiply only [0..3]
         let color: [f32; 4] = (light.color * light.intensity).into();
         LightRaw {
             proj: proj.to_cols_array_2d(),
-            pos: (global_transform.position() / light.range).into(), // dot(pos,pos) is the attenuation.
+            pos: (G::homogeneous(G::position(global_transform)) / light.range).into(), // dot(pos,pos) is the attenuation.
             color,
         }
     }
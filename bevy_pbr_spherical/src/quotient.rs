@@ -0,0 +1,251 @@
+use crate::StandardMaterial;
+use bevy_asset::Handle;
+use bevy_ecs::{
+    entity::Entity,
+    query::{With, Without},
+    system::{Commands, Query, Res},
+};
+use bevy_render_spherical::{
+    draw::{Draw, Visible},
+    mesh::Mesh,
+    pipeline::RenderPipelines,
+    render_graph::base::MainPass,
+};
+use bevy_transform_spherical::{prelude::*, quotient::QuotientGroup};
+
+/// An entity drawn in place of another, moved by an element of the [`QuotientGroup`].
+/// These are spawned and updated automatically; do not add them yourself.
+#[derive(Debug, Clone, Copy)]
+pub struct QuotientImage {
+    /// The entity this is an image of.
+    pub source: Entity,
+    /// The index of the group element in [`QuotientGroup::elements`].
+    pub element: usize,
+}
+
+/// The image entities spawned for a mesh entity,
+/// one for each element of the [`QuotientGroup`] except the identity.
+#[derive(Debug, Clone, Default)]
+pub struct QuotientImages(pub Vec<Entity>);
+
+/// Spawns the images of every mesh entity under the [`QuotientGroup`].
+/// If the group changes, all images are despawned and spawned again;
+/// if it is removed, they are despawned.
+///
+/// Only meshes are imaged. Lights are not, because there are too few light slots to go around.
+#[allow(clippy::type_complexity)]
+pub fn quotient_image_spawn_system(
+    mut commands: Commands,
+    quotient: Option<Res<QuotientGroup>>,
+    images: Query<Entity, With<QuotientImage>>,
+    sources: Query<
+        (
+            Entity,
+            &Handle<Mesh>,
+            &Handle<StandardMaterial>,
+            &RenderPipelines,
+            &Visible,
+            &GlobalTransform,
+            Option<&QuotientImages>,
+        ),
+        (With<MainPass>, Without<QuotientImage>),
+    >,
+) {
+    let respawn = quotient
+        .as_ref()
+        .map_or(true, |quotient| quotient.is_changed());
+    if respawn {
+        for image in images.iter() {
+            commands.entity(image).despawn();
+        }
+    }
+    let quotient = match quotient {
+        Some(quotient) => quotient,
+        None => {
+            for (entity, .., images) in sources.iter() {
+                if images.is_some() {
+                    commands.entity(entity).remove::<QuotientImages>();
+                }
+            }
+            return;
+        }
+    };
+
+    for (entity, mesh, material, render_pipelines, visible, global_transform, images) in
+        sources.iter()
+    {
+        if images.is_some() && !respawn {
+            continue;
+        }
+
+        let images = quotient
+            .elements()
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(element, &g)| {
+                commands
+                    .spawn_bundle((
+                        mesh.clone(),
+                        material.clone(),
+                        MainPass,
+                        Draw::default(),
+                        visible.clone(),
+                        render_pipelines.clone(),
+                        GlobalTransform {
+                            biquat: g * global_transform.biquat,
+                            ..*global_transform
+                        },
+                        QuotientImage {
+                            source: entity,
+                            element,
+                        },
+                    ))
+                    .id()
+            })
+            .collect();
+        commands.entity(entity).insert(QuotientImages(images));
+    }
+}
+
+/// Moves each [`QuotientImage`] to follow its source, and despawns it once its source is gone.
+pub fn quotient_image_update_system(
+    mut commands: Commands,
+    quotient: Option<Res<QuotientGroup>>,
+    mut images: Query<(Entity, &QuotientImage, &mut GlobalTransform, &mut Visible)>,
+    sources: Query<(&GlobalTransform, &Visible), Without<QuotientImage>>,
+) {
+    let quotient = match quotient {
+        Some(quotient) => quotient,
+        None => return,
+    };
+
+    for (entity, image, mut global_transform, mut visible) in images.iter_mut() {
+        let (source_transform, source_visible) = match sources.get(image.source) {
+            Ok(source) => source,
+            Err(_) => {
+                commands.entity(entity).despawn();
+                continue;
+            }
+        };
+        let g = match quotient.elements().get(image.element) {
+            Some(&g) => g,
+            // The group shrank; the spawn system replaces this image.
+            None => continue,
+        };
+        global_transform.biquat = g * source_transform.biquat;
+        global_transform.scale = source_transform.scale;
+        *visible = source_visible.clone();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        system::IntoSystem,
+        world::World,
+    };
+    use bevy_math::{Vec3, Vec4};
+
+    use super::*;
+
+    fn image_count(world: &mut World) -> usize {
+        world
+            .query_filtered::<Entity, With<QuotientImage>>()
+            .iter(world)
+            .count()
+    }
+
+    fn spawn_source(world: &mut World, transform: Transform) -> Entity {
+        world
+            .spawn()
+            .insert_bundle((
+                Handle::<Mesh>::default(),
+                Handle::<StandardMaterial>::default(),
+                RenderPipelines::default(),
+                Visible::default(),
+                MainPass,
+                GlobalTransform::from(transform),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn images_follow_the_group() {
+        let mut world = World::default();
+        world.insert_resource(QuotientGroup::lens_space(5, 2));
+        let source = spawn_source(&mut world, Transform::from_translation(Vec3::X * 0.3));
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(quotient_image_spawn_system.system());
+        stage.run(&mut world);
+        assert_eq!(image_count(&mut world), 4);
+        assert_eq!(world.get::<QuotientImages>(source).unwrap().0.len(), 4);
+
+        // Nothing changed, so nothing is respawned.
+        let images = world.get::<QuotientImages>(source).unwrap().0.clone();
+        stage.run(&mut world);
+        assert_eq!(world.get::<QuotientImages>(source).unwrap().0, images);
+
+        // A smaller group replaces every image, including ones for elements that no longer exist.
+        *world.get_resource_mut::<QuotientGroup>().unwrap() = QuotientGroup::lens_space(3, 1);
+        stage.run(&mut world);
+        assert_eq!(image_count(&mut world), 2);
+        assert!(images
+            .iter()
+            .all(|&image| world.get_entity(image).is_none()));
+        let group = QuotientGroup::lens_space(3, 1);
+        for &image in &world.get::<QuotientImages>(source).unwrap().0 {
+            let QuotientImage { element, .. } = *world.get::<QuotientImage>(image).unwrap();
+            let expected = group.elements()[element]
+                * Transform::from_translation(Vec3::X * 0.3).biquat
+                * Vec4::W;
+            let position = world.get::<GlobalTransform>(image).unwrap().position();
+            assert!(position.abs_diff_eq(expected, 1e-5));
+        }
+
+        // Removing the group removes the images.
+        world.remove_resource::<QuotientGroup>();
+        stage.run(&mut world);
+        assert_eq!(image_count(&mut world), 0);
+        assert!(world.get::<QuotientImages>(source).is_none());
+    }
+
+    #[test]
+    fn images_follow_their_source() {
+        let mut world = World::default();
+        world.insert_resource(QuotientGroup::lens_space(4, 1));
+        let source = spawn_source(&mut world, Transform::identity());
+
+        let mut spawn_stage = SystemStage::parallel();
+        spawn_stage.add_system(quotient_image_spawn_system.system());
+        spawn_stage.run(&mut world);
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(quotient_image_update_system.system());
+
+        let moved = Transform::from_translation(Vec3::new(0.1, -0.2, 0.4));
+        *world.get_mut::<GlobalTransform>(source).unwrap() = GlobalTransform::from(moved);
+        world.get_mut::<Visible>(source).unwrap().is_visible = false;
+        update_stage.run(&mut world);
+
+        let group = QuotientGroup::lens_space(4, 1);
+        let images = world.get::<QuotientImages>(source).unwrap().0.clone();
+        for &image in &images {
+            let element = world.get::<QuotientImage>(image).unwrap().element;
+            let global_transform = world.get::<GlobalTransform>(image).unwrap();
+            assert!(global_transform
+                .biquat
+                .abs_diff_eq(group.elements()[element] * moved.biquat, 1e-5));
+            assert!(!world.get::<Visible>(image).unwrap().is_visible);
+        }
+
+        // Images of a despawned source are despawned.
+        world.despawn(source);
+        update_stage.run(&mut world);
+        assert!(images
+            .iter()
+            .all(|&image| world.get_entity(image).is_none()));
+    }
+}
@@ -13,26 +13,31 @@ use bevy_render_spherical::{
         BufferId, BufferInfo, BufferMapMode, BufferUsage, RenderContext, RenderResourceBinding,
         RenderResourceBindings, RenderResourceContext,
     },
+    space::RenderGeometry,
 };
 use bevy_transform_spherical::prelude::*;
+use std::marker::PhantomData;
 
-/// A Render Graph [Node] that write light data from the ECS to GPU buffers
+/// A Render Graph [Node] that write light data from the ECS to GPU buffers,
+/// placing the lights in the space `G`
 #[derive(Debug, Default)]
-pub struct LightsNode {
+pub struct LightsNode<G: RenderGeometry = Spherical> {
     command_queue: CommandQueue,
     max_lights: usize,
+    geometry: PhantomData<G>,
 }
 
-impl LightsNode {
+impl<G: RenderGeometry> LightsNode<G> {
     pub fn new(max_lights: usize) -> Self {
         LightsNode {
             max_lights,
             command_queue: CommandQueue::default(),
+            geometry: PhantomData,
         }
     }
 }
 
-impl Node for LightsNode {
+impl<G: RenderGeometry> Node for LightsNode<G> {
     fn update(
         &mut self,
         _world: &World,
@@ -52,9 +57,9 @@ struct LightCount {
 
 unsafe impl Byteable for LightCount {}
 
-impl SystemNode for LightsNode {
+impl<G: RenderGeometry> SystemNode for LightsNode<G> {
     fn get_system(&self) -> BoxedSystem {
-        let system = lights_node_system.system().config(|config| {
+        let system = lights_node_system::<G>.system().config(|config| {
             config.0 = Some(LightsNodeSystemState {
                 command_queue: self.command_queue.clone(),
                 max_lights: self.max_lights,
@@ -75,10 +80,11 @@ pub struct LightsNodeSystemState {
     max_lights: usize,
 }
 
-pub fn lights_node_system(
+pub fn lights_node_system<G: RenderGeometry>(
     mut state: Local<LightsNodeSystemState>,
     render_resource_context: Res<Box<dyn RenderResourceContext>>,
     ambient_light_resource: Res<AmbientLight>,
+    universe_radius: Res<UniverseRadius>,
     // TODO: this write on RenderResourceBindings will prevent this system from running in parallel
     // with other systems that do the same
     mut render_resource_bindings: ResMut<RenderResourceBindings>,
@@ -146,7 +152,9 @@ pub fn lights_node_system(
                 .iter()
                 .zip(data[light_count_size..current_light_uniform_size].chunks_exact_mut(size))
             {
-                slot.copy_from_slice(LightRaw::from(&light, &global_transform).as_bytes());
+                slot.copy_from_slice(
+                    LightRaw::from::<G>(&light, &global_transform, *universe_radius).as_bytes(),
+                );
             }
         },
     );
@@ -13,10 +13,11 @@ use bevy_render_spherical::{
     pipeline::PipelineDescriptor,
     render_graph::{base, AssetRenderResourcesNode, RenderGraph, RenderResourcesNode},
     shader::Shader,
+    space::RenderGeometry,
 };
 use bevy_transform_spherical::prelude::GlobalTransform;
 
-pub(crate) fn add_pbr_graph(world: &mut World) {
+pub(crate) fn add_pbr_graph<G: RenderGeometry>(world: &mut World) {
     {
         let mut graph = world.get_resource_mut::<RenderGraph>().unwrap();
         graph.add_system_node(
@@ -27,7 +28,7 @@ pub(crate) fn add_pbr_graph(world: &mut World) {
             node::STANDARD_MATERIAL,
             AssetRenderResourcesNode::<StandardMaterial>::new(true),
         );
-        graph.add_system_node(node::LIGHTS, LightsNode::new(10));
+        graph.add_system_node(node::LIGHTS, LightsNode::<G>::new(10));
 
         // TODO: replace these with "autowire" groups
         graph
@@ -58,6 +58,13 @@ layout(set = 0, binding = 0) uniform CameraViewProj {
 layout(std140, set = 0, binding = 1) uniform CameraPosition {
     vec4 CameraPos;
 };
+layout(std140, set = 0, binding = 2) uniform UniverseRadius {
+    float Radius;
+};
+layout(std140, set = 0, binding = 3) uniform SpaceForm {
+    // Nonzero in elliptic space, where `p` and `-p` are the same point.
+    uint Elliptic;
+};
 
 layout(std140, set = 1, binding = 0) uniform Lights {
     vec4 AmbientColor;
@@ -151,7 +158,9 @@ float getDistanceAttenuation(const float dotPosLight, float inverseRadiusSquared
     //
     // r² + (L·P)² = 1
     //
-    float recip_falloff = 1.0 - dotPosLight * dotPosLight;
+    // Scale r by the radius of the universe, so that it is measured in world units,
+    // like the light's range.
+    float recip_falloff = (1.0 - dotPosLight * dotPosLight) * Radius * Radius;
     float factor = recip_falloff * inverseRadiusSquared;
     float smoothFactor = saturate(1.0 - factor * factor);
     float attenuation = smoothFactor * smoothFactor;
@@ -370,12 +379,18 @@ void main() {
     for (int i = 0; i < int(NumLights.x) && i < MAX_LIGHTS; ++i) {
         Light light = SceneLights[i];
 
-        vec4 L = normalize(light.pos) - WorldPosition;
+        vec4 lightPos = normalize(light.pos);
+        // In elliptic space, light from the nearer representative of the light.
+        if (Elliptic != 0 && dot(lightPos, WorldPosition) < 0.0) {
+            lightPos = -lightPos;
+        }
+
+        vec4 L = lightPos - WorldPosition;
         L -= WorldPosition * dot(L, WorldPosition);
         L = normalize(L);
 
         float rangeAttenuation =
-            getDistanceAttenuation(dot(WorldPosition, normalize(light.pos)), dot(light.pos, light.pos));
+            getDistanceAttenuation(dot(WorldPosition, lightPos), dot(light.pos, light.pos));
 
         vec4 H = normalize(L + V);
         float NoL = saturate(dot(N, L));
@@ -15,6 +15,16 @@ layout(location = 2) out vec2 v_Uv;
 layout(set = 0, binding = 0) uniform CameraViewProj {
     mat4 ViewProj;
 };
+layout(std140, set = 0, binding = 1) uniform CameraPosition {
+    vec4 CameraPos;
+};
+layout(std140, set = 0, binding = 2) uniform UniverseRadius {
+    float Radius;
+};
+layout(std140, set = 0, binding = 3) uniform SpaceForm {
+    // Nonzero in elliptic space, where `p` and `-p` are the same point.
+    uint Elliptic;
+};
 
 // #ifdef STANDARDMATERIAL_NORMAL_MAP
 // layout(location = 3) out vec4 v_WorldTangent;
@@ -22,12 +32,22 @@ layout(set = 0, binding = 0) uniform CameraViewProj {
 
 layout(set = 2, binding = 0) uniform Transform {
     mat4 Model;
+    float Scale;
 };
 
 void main() {
-    vec4 world_position = Model * Vertex_Position;
+    // Meshes are authored in world units; the model matrix expects units of the radius.
+    vec4 world_position = Model * vec4(Vertex_Position.xyz * Scale / Radius, Vertex_Position.w);
+    // The inverse transpose of `diag(Scale, Scale, Scale, 1)`, up to a factor.
+    vec4 world_normal = Model * vec4(Vertex_Normal.xyz, Vertex_Normal.w * Scale);
+    // In elliptic space, draw each object using the representative on the camera's side of the
+    // horizon. Flipping per object rather than per vertex keeps triangles in one piece.
+    if (Elliptic != 0 && dot(Model[3], CameraPos) < 0.0) {
+        world_position = -world_position;
+        world_normal = -world_normal;
+    }
     v_WorldPosition = world_position;
-    v_WorldNormal = Model * Vertex_Normal;
+    v_WorldNormal = world_normal;
     v_Uv = Vertex_Uv;
 // #ifdef STANDARDMATERIAL_NORMAL_MAP
 //     v_WorldTangent = vec4(mat3(Model) * Vertex_Tangent.xyz, Vertex_Tangent.w);
@@ -1,7 +1,7 @@
 pub use bevy_render::camera::{CameraProjection, ScalingMode, WindowOrigin};
 
 use super::DepthCalculation;
-use bevy_ecs::reflect::ReflectComponent;
+use bevy_ecs::{query::Changed, reflect::ReflectComponent, schedule::SystemLabel, system::Query};
 use bevy_math::{Mat4, Vec4};
 use bevy_reflect::Reflect;
 
@@ -13,6 +13,12 @@ pub struct PerspectiveProjection {
     /// The tangent of the distance to the near plane.
     pub tan_near: f32,
     /// The tangent of the distance to the far plane.
+    ///
+    /// A negative value puts the far plane beyond distance π/2, up to just before the antipode.
+    /// In [elliptic space](bevy_transform_spherical::universe::SpaceForm::Elliptic),
+    /// [`elliptic_projection_system`] replaces a negative value with `f32::INFINITY`,
+    /// which clips at the projective horizon, distance π/2.
+    /// Everything beyond the horizon is also visible in the opposite direction, on this side of it.
     pub tan_far: f32,
 }
 
@@ -23,7 +29,12 @@ impl CameraProjection for PerspectiveProjection {
             let (sin_fov, cos_fov) = (0.5 * fov_y_radians).sin_cos();
             let h = cos_fov / sin_fov;
             let w = h / aspect_ratio;
-            let r = z_far / (z_near - z_far);
+            // As `z_far` goes to infinity, `r` goes to -1.
+            let r = if z_far.is_infinite() {
+                -1.
+            } else {
+                z_far / (z_near - z_far)
+            };
             Mat4::from_cols(
                 Vec4::new(w, 0., 0., 0.),
                 Vec4::new(0., h, 0., 0.),
@@ -54,3 +65,82 @@ impl Default for PerspectiveProjection {
         }
     }
 }
+
+/// Labels for the camera systems that `RenderPlugin` adds.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum CameraSystem {
+    /// Fits each [`PerspectiveProjection`] to elliptic space, in [`elliptic_projection_system`].
+    /// Runs before the projection matrices are computed.
+    EllipticProjection,
+}
+
+/// Moves the far plane of each changed [`PerspectiveProjection`] that reaches beyond
+/// the projective horizon back to it, by setting [`PerspectiveProjection::tan_far`] to infinity.
+///
+/// `RenderPlugin` adds this in [elliptic space](bevy_transform_spherical::universe::SpaceForm::Elliptic),
+/// where every point is within distance π/2, the horizon, so a far plane beyond it
+/// would only spread the depth buffer over distances that are never drawn.
+pub fn elliptic_projection_system(
+    mut query: Query<&mut PerspectiveProjection, Changed<PerspectiveProjection>>,
+) {
+    for mut projection in query.iter_mut() {
+        if projection.tan_far < 0. {
+            projection.tan_far = f32::INFINITY;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        system::IntoSystem,
+        world::World,
+    };
+
+    use super::*;
+
+    /// The depth of the point straight ahead at distance `distance`, after the perspective divide.
+    fn depth(projection: &PerspectiveProjection, distance: f32) -> f32 {
+        let (sin, cos) = distance.sin_cos();
+        let clip = projection.get_projection_matrix() * Vec4::new(0., 0., -sin, cos);
+        clip.z / clip.w
+    }
+
+    #[test]
+    fn elliptic_far_plane_is_the_horizon() {
+        let mut world = World::default();
+        let camera = world.spawn().insert(PerspectiveProjection::default()).id();
+        let finite = world
+            .spawn()
+            .insert(PerspectiveProjection {
+                tan_far: 0.5,
+                ..Default::default()
+            })
+            .id();
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(elliptic_projection_system.system());
+        stage.run(&mut world);
+
+        let projection = world.get::<PerspectiveProjection>(camera).unwrap().clone();
+        assert_eq!(projection.tan_far, f32::INFINITY);
+        // A far plane nearer than the horizon is kept.
+        assert_eq!(
+            world.get::<PerspectiveProjection>(finite).unwrap().tan_far,
+            0.5
+        );
+
+        // Depth increases from 0 at the near plane to 1 at the horizon, and no further.
+        let near = projection.tan_near.atan();
+        assert!(depth(&projection, near).abs() < 1e-4);
+        let mut previous = 0.;
+        for k in 1..=100 {
+            let distance = near + (std::f32::consts::FRAC_PI_2 - near) * k as f32 / 100.;
+            let depth = depth(&projection, distance);
+            assert!(depth > previous && depth <= 1., "{} at {}", depth, distance);
+            previous = depth;
+        }
+        assert!(depth(&projection, std::f32::consts::FRAC_PI_2 + 0.01) > 1.);
+    }
+}
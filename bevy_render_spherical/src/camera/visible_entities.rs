@@ -2,26 +2,27 @@ use super::{Camera, DepthCalculation};
 use crate::{draw::OutsideFrustum, prelude::Visible};
 use bevy_core::FloatOrd;
 use bevy_ecs::{entity::Entity, query::Without, system::Query};
-use bevy_transform_spherical::prelude::GlobalTransform;
+use bevy_transform_spherical::space::Geometry;
 use bevy_utils::tracing::warn;
 
 pub use bevy_render::camera::{Layer, RenderLayers, VisibleEntities, VisibleEntity};
 
-pub fn visible_entities_system(
+/// Collects and sorts the entities each camera can see, measuring depth in the geometry `G`.
+pub fn visible_entities_system<G: Geometry>(
     mut camera_query: Query<(
         &Camera,
-        &GlobalTransform,
+        &G::GlobalTransform,
         &mut VisibleEntities,
         Option<&RenderLayers>,
     )>,
     visible_query: Query<(Entity, &Visible, Option<&RenderLayers>), Without<OutsideFrustum>>,
-    visible_transform_query: Query<&GlobalTransform, Without<OutsideFrustum>>,
+    visible_transform_query: Query<&G::GlobalTransform, Without<OutsideFrustum>>,
 ) {
     for (camera, camera_global_transform, mut visible_entities, maybe_camera_mask) in
         camera_query.iter_mut()
     {
         visible_entities.value.clear();
-        let camera_position = camera_global_transform.position();
+        let camera_position = G::position(camera_global_transform);
         let camera_mask = maybe_camera_mask.copied().unwrap_or_default();
 
         let mut no_transform_order = 0.0;
@@ -37,14 +38,14 @@ pub fn visible_entities_system(
             }
 
             let order = if let Ok(global_transform) = visible_transform_query.get(entity) {
-                let position = global_transform.position();
+                let position = G::position(global_transform);
                 // smaller distances are sorted to lower indices by using the distance from the
                 // camera
                 FloatOrd(match camera.depth_calculation {
-                    DepthCalculation::Distance => (camera_position - position).length_squared(),
+                    DepthCalculation::Distance => G::distance(camera_position, position),
                     DepthCalculation::ZDifference => {
-                        warn!("The ZDifference depth calculation does not make sense in spherical space!");
-                        (camera_position - position).length_squared()
+                        warn!("The ZDifference depth calculation does not make sense in curved space!");
+                        G::distance(camera_position, position)
                     }
                 })
             } else {
@@ -6,6 +6,7 @@ pub use bevy_render::{
 pub mod camera;
 pub mod entity;
 pub mod render_graph;
+pub mod space;
 pub mod wireframe;
 
 pub mod prelude {
@@ -29,10 +30,12 @@ use bevy_ecs::{
     schedule::{ParallelSystemDescriptorCoercion, SystemStage},
     system::{IntoExclusiveSystem, IntoSystem},
 };
-use bevy_transform_spherical::TransformSystem;
+use bevy_transform_spherical::{
+    space::Spherical, universe::SpaceForm, InterpolationSystem, TransformSystem,
+};
 use camera::{
-    ActiveCameras, Camera, DepthCalculation, PerspectiveProjection, RenderLayers, ScalingMode,
-    VisibleEntities, WindowOrigin,
+    ActiveCameras, Camera, CameraSystem, DepthCalculation, PerspectiveProjection, RenderLayers,
+    ScalingMode, VisibleEntities, WindowOrigin,
 };
 use draw::OutsideFrustum;
 use pipeline::{
@@ -45,27 +48,34 @@ use render_graph::{
 };
 use renderer::{AssetRenderResourceBindings, RenderResourceBindings};
 use shader::ShaderLoader;
+use space::RenderGeometry;
+use std::marker::PhantomData;
 #[cfg(feature = "hdr")]
 use texture::HdrTextureLoader;
 #[cfg(feature = "png")]
 use texture::ImageTextureLoader;
 
-/// Adds core render types and systems to an App
-pub struct RenderPlugin {
+/// Adds core render types and systems to an App, drawing the space `G`.
+///
+/// Also inserts the matching [`SpaceForm`] resource.
+pub struct RenderPlugin<G: RenderGeometry = Spherical> {
     /// configures the "base render graph". If this is not `None`, the "base render graph" will be
     /// added
     pub base_render_graph_config: Option<BaseRenderGraphConfig>,
+    /// The space drawn, such as [`Spherical`] or [`Elliptic`](bevy_transform_spherical::space::Elliptic).
+    pub geometry: PhantomData<G>,
 }
 
-impl Default for RenderPlugin {
+impl<G: RenderGeometry> Default for RenderPlugin<G> {
     fn default() -> Self {
         RenderPlugin {
             base_render_graph_config: Some(BaseRenderGraphConfig::default()),
+            geometry: PhantomData,
         }
     }
 }
 
-impl Plugin for RenderPlugin {
+impl<G: RenderGeometry> Plugin for RenderPlugin<G> {
     fn build(&self, app: &mut AppBuilder) {
         #[cfg(feature = "png")]
         {
@@ -136,19 +146,6 @@ impl Plugin for RenderPlugin {
             CoreStage::PostUpdate,
             camera::active_cameras_system.system(),
         )
-        .add_system_to_stage(
-            CoreStage::PostUpdate,
-            camera::camera_system::<PerspectiveProjection>
-                .system()
-                .before(RenderSystem::VisibleEntities),
-        )
-        .add_system_to_stage(
-            CoreStage::PostUpdate,
-            camera::visible_entities_system
-                .system()
-                .label(RenderSystem::VisibleEntities)
-                .after(TransformSystem::TransformPropagate),
-        )
         .add_system_to_stage(
             RenderStage::RenderResource,
             shader::shader_update_system.system(),
@@ -174,6 +171,38 @@ impl Plugin for RenderPlugin {
             shader::clear_shader_defs_system.system(),
         );
 
+        app.insert_resource(G::SPACE_FORM);
+        match G::SPACE_FORM {
+            SpaceForm::Spherical => app.add_system_to_stage(
+                CoreStage::PostUpdate,
+                camera::camera_system::<PerspectiveProjection>
+                    .system()
+                    .before(RenderSystem::VisibleEntities),
+            ),
+            SpaceForm::Elliptic => app
+                .add_system_to_stage(
+                    CoreStage::PostUpdate,
+                    camera::elliptic_projection_system
+                        .system()
+                        .label(CameraSystem::EllipticProjection),
+                )
+                .add_system_to_stage(
+                    CoreStage::PostUpdate,
+                    camera::camera_system::<PerspectiveProjection>
+                        .system()
+                        .after(CameraSystem::EllipticProjection)
+                        .before(RenderSystem::VisibleEntities),
+                ),
+        };
+        app.add_system_to_stage(
+            CoreStage::PostUpdate,
+            camera::visible_entities_system::<G>
+                .system()
+                .label(RenderSystem::VisibleEntities)
+                .after(TransformSystem::TransformPropagate)
+                .after(InterpolationSystem::TransformInterpolate),
+        );
+
         if let Some(ref config) = self.base_render_graph_config {
             crate::base::add_base_graph(config, app.world_mut());
             let mut active_cameras = app.world_mut().get_resource_mut::<ActiveCameras>().unwrap();
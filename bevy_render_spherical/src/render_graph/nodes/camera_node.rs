@@ -60,6 +60,8 @@ impl SystemNode for CameraNode {
 const CAMERA_VIEW_PROJ: &str = "CameraViewProj";
 const CAMERA_VIEW: &str = "CameraView";
 const CAMERA_POSITION: &str = "CameraPosition";
+const UNIVERSE_RADIUS: &str = "UniverseRadius";
+const SPACE_FORM: &str = "SpaceForm";
 
 #[derive(Debug, Default)]
 pub struct CameraNodeState {
@@ -75,6 +77,8 @@ pub fn camera_node_system(
     mut state: Local<CameraNodeState>,
     mut active_cameras: ResMut<ActiveCameras>,
     render_resource_context: Res<Box<dyn RenderResourceContext>>,
+    universe_radius: Res<UniverseRadius>,
+    space_form: Res<SpaceForm>,
     mut query: Query<(&Camera, &GlobalTransform)>,
 ) {
     let render_resource_context = &**render_resource_context;
@@ -101,6 +105,10 @@ pub fn camera_node_system(
                 // View
                 MATRIX_SIZE +
                 // Position
+                VEC4_SIZE +
+                // Radius
+                VEC4_SIZE +
+                // Space form
                 VEC4_SIZE,
             buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
             mapped_at_creation: true,
@@ -158,6 +166,38 @@ pub fn camera_node_system(
         );
     }
 
+    if bindings.get(UNIVERSE_RADIUS).is_none() {
+        let buffer = render_resource_context.create_buffer(BufferInfo {
+            size: VEC4_SIZE,
+            buffer_usage: BufferUsage::COPY_DST | BufferUsage::UNIFORM,
+            ..Default::default()
+        });
+        bindings.set(
+            UNIVERSE_RADIUS,
+            RenderResourceBinding::Buffer {
+                buffer,
+                range: 0..VEC4_SIZE as u64,
+                dynamic_index: None,
+            },
+        );
+    }
+
+    if bindings.get(SPACE_FORM).is_none() {
+        let buffer = render_resource_context.create_buffer(BufferInfo {
+            size: VEC4_SIZE,
+            buffer_usage: BufferUsage::COPY_DST | BufferUsage::UNIFORM,
+            ..Default::default()
+        });
+        bindings.set(
+            SPACE_FORM,
+            RenderResourceBinding::Buffer {
+                buffer,
+                range: 0..VEC4_SIZE as u64,
+                dynamic_index: None,
+            },
+        );
+    }
+
     let view = global_transform.compute_matrix();
     let mut offset = 0;
 
@@ -214,6 +254,45 @@ pub fn camera_node_system(
             0,
             VEC4_SIZE as u64,
         );
+        offset += VEC4_SIZE as u64;
+    }
+
+    if let Some(RenderResourceBinding::Buffer { buffer, .. }) = bindings.get(UNIVERSE_RADIUS) {
+        // std140 pads the float to a vec4.
+        let radius = [universe_radius.0, 0., 0., 0.];
+        render_resource_context.write_mapped_buffer(
+            staging_buffer,
+            offset..(offset + VEC4_SIZE as u64),
+            &mut |data, _renderer| {
+                data[0..VEC4_SIZE].copy_from_slice(radius.as_bytes());
+            },
+        );
+        state.command_queue.copy_buffer_to_buffer(
+            staging_buffer,
+            offset,
+            *buffer,
+            0,
+            VEC4_SIZE as u64,
+        );
+        offset += VEC4_SIZE as u64;
+    }
+
+    if let Some(RenderResourceBinding::Buffer { buffer, .. }) = bindings.get(SPACE_FORM) {
+        let elliptic = [(*space_form == SpaceForm::Elliptic) as u32, 0, 0, 0];
+        render_resource_context.write_mapped_buffer(
+            staging_buffer,
+            offset..(offset + VEC4_SIZE as u64),
+            &mut |data, _renderer| {
+                data[0..VEC4_SIZE].copy_from_slice(elliptic.as_bytes());
+            },
+        );
+        state.command_queue.copy_buffer_to_buffer(
+            staging_buffer,
+            offset,
+            *buffer,
+            0,
+            VEC4_SIZE as u64,
+        );
     }
 
     render_resource_context.unmap_buffer(staging_buffer);
@@ -0,0 +1,25 @@
+use bevy_math::Vec4;
+use bevy_transform_spherical::{
+    components::{GlobalTransform, Transform},
+    space::{Elliptic, Geometry, Spherical},
+    universe::SpaceForm,
+};
+
+/// A [`Geometry`] that this crate's shaders can draw.
+///
+/// The shaders work in the 3-sphere, so only [`Spherical`] and [`Elliptic`] space qualify.
+/// Flat space is still drawn by Bevy's own `RenderPlugin`.
+pub trait RenderGeometry:
+    Geometry<Transform = Transform, GlobalTransform = GlobalTransform, Point = Vec4>
+{
+    /// The [`SpaceForm`] resource that [`RenderPlugin`](crate::RenderPlugin) inserts for the shaders.
+    const SPACE_FORM: SpaceForm;
+}
+
+impl RenderGeometry for Spherical {
+    const SPACE_FORM: SpaceForm = SpaceForm::Spherical;
+}
+
+impl RenderGeometry for Elliptic {
+    const SPACE_FORM: SpaceForm = SpaceForm::Elliptic;
+}
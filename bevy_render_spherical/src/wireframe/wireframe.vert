@@ -5,11 +5,26 @@ layout(location = 0) in vec4 Vertex_Position;
 layout(set = 0, binding = 0) uniform CameraViewProj {
     mat4 ViewProj;
 };
+layout(std140, set = 0, binding = 1) uniform CameraPosition {
+    vec4 CameraPos;
+};
+layout(std140, set = 0, binding = 2) uniform UniverseRadius {
+    float Radius;
+};
+layout(std140, set = 0, binding = 3) uniform SpaceForm {
+    // Nonzero in elliptic space, where `p` and `-p` are the same point.
+    uint Elliptic;
+};
 
 layout(set = 1, binding = 0) uniform Transform {
     mat4 Model;
+    float Scale;
 };
 
 void main() {
-    gl_Position = ViewProj * Model * Vertex_Position;
+    vec4 world_position = Model * vec4(Vertex_Position.xyz * Scale / Radius, Vertex_Position.w);
+    if (Elliptic != 0 && dot(Model[3], CameraPos) < 0.0) {
+        world_position = -world_position;
+    }
+    gl_Position = ViewProj * world_position;
 }
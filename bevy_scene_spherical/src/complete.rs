@@ -0,0 +1,97 @@
+use bevy_asset::Handle;
+use bevy_ecs::{
+    entity::Entity,
+    query::{Or, With, Without},
+    system::{Commands, Query},
+};
+use bevy_pbr_spherical::{render_graph::PBR_PIPELINE_HANDLE, StandardMaterial};
+use bevy_render_spherical::{
+    camera::{Camera, VisibleEntities},
+    draw::{Draw, Visible},
+    mesh::Mesh,
+    pipeline::{RenderPipeline, RenderPipelines},
+    render_graph::base::MainPass,
+};
+use bevy_transform_spherical::components::{
+    GlobalTransform, HyperbolicGlobalTransform, HyperbolicTransform, Transform,
+};
+
+/// Gives entities the components that [`scene_from_world`](crate::scene_from_world)
+/// leaves out, and fills in the rest of a bundle that a scene file only partly writes.
+///
+/// * Each [`Transform`] gets a [`GlobalTransform`], and each [`HyperbolicTransform`]
+///   a [`HyperbolicGlobalTransform`], which are set when transforms propagate.
+/// * Each mesh gets the rest of a [`PbrBundle`](bevy_pbr_spherical::PbrBundle), or of a
+///   [`MeshBundle`](bevy_render_spherical::entity::MeshBundle) if it has no material.
+/// * Each [`Camera`] gets its [`VisibleEntities`].
+///
+/// So a level can be written by hand with only the `Transform`, `Handle<Mesh>` and
+/// `Handle<StandardMaterial>` of each object.
+#[allow(clippy::type_complexity)]
+pub fn scene_complete_system(
+    mut commands: Commands,
+    transform_query: Query<Entity, (With<Transform>, Without<GlobalTransform>)>,
+    hyperbolic_query: Query<
+        Entity,
+        (
+            With<HyperbolicTransform>,
+            Without<HyperbolicGlobalTransform>,
+        ),
+    >,
+    mesh_query: Query<
+        (
+            Entity,
+            Option<&Handle<StandardMaterial>>,
+            Option<&Visible>,
+            Option<&MainPass>,
+            Option<&RenderPipelines>,
+        ),
+        (With<Handle<Mesh>>, Without<Draw>),
+    >,
+    camera_query: Query<Entity, (With<Camera>, Without<VisibleEntities>)>,
+    untransformed_query: Query<
+        Entity,
+        (
+            Or<(With<Handle<Mesh>>, With<Camera>)>,
+            Without<Transform>,
+            Without<HyperbolicTransform>,
+        ),
+    >,
+) {
+    for entity in transform_query.iter() {
+        commands.entity(entity).insert(GlobalTransform::identity());
+    }
+    for entity in hyperbolic_query.iter() {
+        commands
+            .entity(entity)
+            .insert(HyperbolicGlobalTransform::identity());
+    }
+    for (entity, material, visible, main_pass, render_pipelines) in mesh_query.iter() {
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(Draw::default());
+        if visible.is_none() {
+            entity_commands.insert(Visible::default());
+        }
+        if main_pass.is_none() {
+            entity_commands.insert(MainPass);
+        }
+        if render_pipelines.is_none() {
+            entity_commands.insert(if material.is_some() {
+                RenderPipelines::from_pipelines(vec![RenderPipeline::new(
+                    PBR_PIPELINE_HANDLE.typed(),
+                )])
+            } else {
+                RenderPipelines::default()
+            });
+        }
+    }
+    for entity in camera_query.iter() {
+        commands.entity(entity).insert(VisibleEntities::default());
+    }
+    // A mesh or camera written without a position is at the origin.
+    for entity in untransformed_query.iter() {
+        commands
+            .entity(entity)
+            .insert_bundle((Transform::identity(), GlobalTransform::identity()));
+    }
+}
@@ -0,0 +1,229 @@
+pub use bevy_scene::{
+    serde, DynamicScene, Scene, SceneLoader, SceneSpawnError, SceneSpawner,
+    SpawnSceneAsChildCommands, SpawnSceneCommands,
+};
+
+mod complete;
+mod save;
+
+pub use complete::*;
+pub use save::*;
+
+pub mod prelude {
+    pub use crate::{
+        save::{save_scene_ron, scene_from_world},
+        DynamicScene, Scene, SceneSpawner, SpawnSceneAsChildCommands, SpawnSceneCommands,
+    };
+}
+
+use bevy_app::prelude::*;
+use bevy_asset::Handle;
+use bevy_ecs::{
+    schedule::{StageLabel, SystemStage},
+    system::IntoSystem,
+};
+use bevy_pbr_spherical::StandardMaterial;
+use bevy_render_spherical::mesh::Mesh;
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
+pub enum SceneStage {
+    /// Runs right after [`CoreStage::PreUpdate`], where scenes are spawned,
+    /// and gives the spawned entities back the components that are not saved.
+    /// See [`scene_from_world`].
+    Complete,
+}
+
+/// Loads and spawns scenes of spherical entities, written in RON.
+///
+/// This adds [`bevy_scene::ScenePlugin`], so do not add that as well.
+/// Add it after the transform, render and pbr plugins, whose types it saves and loads.
+///
+/// Handles are saved by id, so a mesh or material handle only loads again if it names the
+/// same asset in the next run: an asset loaded from a path, or one added with
+/// `Assets::set` under a fixed id, such as `HandleUntyped::weak_from_u64`.
+/// Handles from `Assets::add` are random, and load as handles to nothing.
+#[derive(Default)]
+pub struct ScenePlugin;
+
+impl Plugin for ScenePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_plugin(bevy_scene::ScenePlugin)
+            .register_type::<Handle<Mesh>>()
+            .register_type::<Handle<StandardMaterial>>()
+            .add_stage_after(
+                CoreStage::PreUpdate,
+                SceneStage::Complete,
+                SystemStage::parallel(),
+            )
+            .add_system_to_stage(
+                SceneStage::Complete,
+                complete::scene_complete_system.system(),
+            );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ops::Range;
+
+    use ::serde::de::DeserializeSeed;
+    use bevy_asset::{HandleId, HandleUntyped};
+    use bevy_ecs::{
+        entity::{Entity, EntityMap},
+        schedule::{Stage, SystemStage},
+        system::IntoSystem,
+        world::World,
+    };
+    use bevy_math::{Mat4, Vec3};
+    use bevy_pbr_spherical::Light;
+    use bevy_reflect::{TypeRegistryArc, TypeUuid};
+    use bevy_render_spherical::{
+        camera::{Camera, PerspectiveProjection, VisibleEntities},
+        color::Color,
+        draw::{Draw, Visible},
+        pipeline::RenderPipelines,
+        render_graph::base::MainPass,
+    };
+    use bevy_transform_spherical::{
+        biquaternion::Biquaternion,
+        components::{GlobalTransform, Transform},
+    };
+
+    use super::{
+        complete::scene_complete_system, save::save_scene_ron, serde::SceneDeserializer, Handle,
+        Mesh, StandardMaterial,
+    };
+
+    /// The types that the plugins would register, for the components used below.
+    fn type_registry() -> TypeRegistryArc {
+        let type_registry = TypeRegistryArc::default();
+        {
+            let mut registry = type_registry.write();
+            registry.register::<bool>();
+            registry.register::<f32>();
+            registry.register::<Option<String>>();
+            registry.register::<Range<f32>>();
+            registry.register::<Mat4>();
+            registry.register::<HandleId>();
+            registry.register::<Handle<Mesh>>();
+            registry.register::<Handle<StandardMaterial>>();
+            registry.register::<Biquaternion>();
+            registry.register::<Transform>();
+            registry.register::<GlobalTransform>();
+            registry.register::<Color>();
+            registry.register::<Light>();
+            registry.register::<Camera>();
+            registry.register::<PerspectiveProjection>();
+            registry.register::<VisibleEntities>();
+            registry.register::<Draw>();
+            registry.register::<Visible>();
+            registry.register::<MainPass>();
+        }
+        type_registry
+    }
+
+    #[test]
+    fn save_load_and_complete() {
+        let type_registry = type_registry();
+        let mut world = World::default();
+        world.insert_resource(type_registry.clone());
+
+        let mesh: Handle<Mesh> = HandleUntyped::weak_from_u64(Mesh::TYPE_UUID, 1).typed();
+        let material: Handle<StandardMaterial> =
+            HandleUntyped::weak_from_u64(StandardMaterial::TYPE_UUID, 2).typed();
+        let transform = Transform::from_translation(Vec3::new(0.3, -0.1, 0.2));
+        world.spawn().insert_bundle((
+            mesh.clone(),
+            material.clone(),
+            transform,
+            GlobalTransform::from(transform),
+            Draw::default(),
+            Visible::default(),
+            MainPass,
+        ));
+        world.spawn().insert_bundle((
+            Light {
+                intensity: 50.,
+                depth: 0.1..5.,
+                ..Default::default()
+            },
+            Transform::from_translation(Vec3::Y),
+            GlobalTransform::identity(),
+        ));
+        world.spawn().insert_bundle((
+            Camera::default(),
+            PerspectiveProjection {
+                fov: 1.2,
+                ..Default::default()
+            },
+            VisibleEntities::default(),
+            Transform::identity(),
+            GlobalTransform::identity(),
+        ));
+        // Nothing but derived components, so not saved at all.
+        world
+            .spawn()
+            .insert_bundle((GlobalTransform::identity(), Draw::default()));
+
+        // Derived components are left out of the file.
+        let ron = save_scene_ron(&world).unwrap();
+        for derived in &["GlobalTransform", "::Draw", "VisibleEntities"] {
+            assert!(!ron.contains(derived), "{} in {}", derived, ron);
+        }
+
+        let scene = {
+            let registry = type_registry.read();
+            let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+            SceneDeserializer {
+                type_registry: &registry,
+            }
+            .deserialize(&mut deserializer)
+            .unwrap()
+        };
+        assert_eq!(scene.entities.len(), 3);
+
+        let mut loaded = World::default();
+        loaded.insert_resource(type_registry);
+        scene
+            .write_to_world(&mut loaded, &mut EntityMap::default())
+            .unwrap();
+        let mut stage = SystemStage::parallel();
+        stage.add_system(scene_complete_system.system());
+        stage.run(&mut loaded);
+        assert_eq!(loaded.query::<Entity>().iter(&loaded).count(), 3);
+
+        // Handles come back by id, and the derived components are restored.
+        let mut meshes = loaded.query::<(
+            &Handle<Mesh>,
+            &Handle<StandardMaterial>,
+            &Transform,
+            &GlobalTransform,
+            &Draw,
+            &RenderPipelines,
+        )>();
+        let (loaded_mesh, loaded_material, loaded_transform, _, _, render_pipelines) =
+            meshes.iter(&loaded).next().unwrap();
+        assert_eq!(*loaded_mesh, mesh);
+        assert_eq!(*loaded_material, material);
+        assert!(loaded_transform.biquat.abs_diff_eq(transform.biquat, 1e-6));
+        assert_eq!(render_pipelines.pipelines.len(), 1);
+
+        let mut lights = loaded.query::<(&Light, &Transform, &GlobalTransform)>();
+        let (light, light_transform, _) = lights.iter(&loaded).next().unwrap();
+        assert_eq!(light.intensity, 50.);
+        assert_eq!(light.depth, 0.1..5.);
+        assert!(light_transform
+            .biquat
+            .abs_diff_eq(Transform::from_translation(Vec3::Y).biquat, 1e-6));
+
+        let mut cameras = loaded.query::<(
+            &Camera,
+            &PerspectiveProjection,
+            &VisibleEntities,
+            &GlobalTransform,
+        )>();
+        let (_, projection, ..) = cameras.iter(&loaded).next().unwrap();
+        assert_eq!(projection.fov, 1.2);
+        assert_eq!(projection.tan_far, PerspectiveProjection::default().tan_far);
+    }
+}
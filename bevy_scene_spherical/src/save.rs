@@ -0,0 +1,83 @@
+use std::any::TypeId;
+
+use bevy_ecs::{reflect::ReflectComponent, world::World};
+use bevy_pbr_spherical::QuotientImage;
+use bevy_reflect::TypeRegistryArc;
+use bevy_render_spherical::{
+    camera::VisibleEntities,
+    draw::{Draw, OutsideFrustum},
+};
+use bevy_scene::{DynamicScene, Entity};
+use bevy_transform_spherical::components::{
+    GlobalTransform, HyperbolicGlobalTransform, PreviousParent,
+};
+
+/// Components that are computed from the others every frame, and so are left out of saved scenes.
+/// [`scene_complete_system`](crate::scene_complete_system) adds them back.
+fn derived_components() -> [TypeId; 6] {
+    [
+        TypeId::of::<GlobalTransform>(),
+        TypeId::of::<HyperbolicGlobalTransform>(),
+        TypeId::of::<PreviousParent>(),
+        TypeId::of::<Draw>(),
+        TypeId::of::<OutsideFrustum>(),
+        TypeId::of::<VisibleEntities>(),
+    ]
+}
+
+/// Like [`DynamicScene::from_world`], but only keeps what is needed to rebuild the scene.
+///
+/// Derived components, such as [`GlobalTransform`] and [`Draw`], are left out,
+/// and so are the [`QuotientImage`]s, which are spawned again from their sources.
+/// Entities with nothing left to save are dropped.
+pub fn scene_from_world(world: &World, type_registry: &TypeRegistryArc) -> DynamicScene {
+    let derived = derived_components();
+    let quotient_image = world.components().get_id(TypeId::of::<QuotientImage>());
+
+    let mut scene = DynamicScene::default();
+    let type_registry = type_registry.read();
+    for archetype in world.archetypes().iter() {
+        if quotient_image.map_or(false, |id| archetype.contains(id)) {
+            continue;
+        }
+
+        let reflect_components: Vec<&ReflectComponent> = archetype
+            .components()
+            .filter_map(|component_id| {
+                let type_id = world.components().get_info(component_id)?.type_id()?;
+                if derived.contains(&type_id) {
+                    return None;
+                }
+                type_registry.get(type_id)?.data::<ReflectComponent>()
+            })
+            .collect();
+        if reflect_components.is_empty() {
+            continue;
+        }
+
+        for &entity in archetype.entities() {
+            scene.entities.push(Entity {
+                entity: entity.id(),
+                components: reflect_components
+                    .iter()
+                    .filter_map(|reflect_component| {
+                        reflect_component.reflect_component(world, entity)
+                    })
+                    .map(|component| component.clone_value())
+                    .collect(),
+            });
+        }
+    }
+
+    scene
+}
+
+/// Saves the entities of `world` as a RON scene, which [`bevy_scene::SceneLoader`]
+/// loads from files ending in `.scn.ron`. See [`scene_from_world`].
+///
+/// Transforms are written as a translation and a rotation;
+/// see [`Biquaternion`](bevy_transform_spherical::biquaternion::Biquaternion).
+pub fn save_scene_ron(world: &World) -> Result<String, ron::Error> {
+    let type_registry = world.get_resource::<TypeRegistryArc>().unwrap();
+    scene_from_world(world, type_registry).serialize_ron(type_registry)
+}
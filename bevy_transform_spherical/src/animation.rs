@@ -0,0 +1,445 @@
+use bevy_math::Vec3;
+use bevy_reflect::TypeUuid;
+
+use crate::{biquaternion::Biquaternion, components::Transform};
+
+/// How an [`AnimationClip`] moves between its keyframes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyframeInterpolation {
+    /// Jump to each keyframe when its time comes, and hold it until the next one.
+    Step,
+    /// Move along the shortest path between neighboring keyframes, at constant speed.
+    /// A clip of translations along one geodesic moves along that geodesic.
+    #[default]
+    Slerp,
+    /// Move along a smooth curve through the keyframes, using spherical quadrangle
+    /// interpolation ("squad") on the two quaternions of each [`Biquaternion`].
+    /// The velocity is continuous at each keyframe except the first and last.
+    ///
+    /// The curve does not take the times of the keyframes into account,
+    /// so it is smoothest when they are evenly spaced.
+    Cubic,
+}
+
+/// A keyframe of an [`AnimationClip`]: where a [`Transform`] should be at `time`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    /// The time of the keyframe, in seconds from the start of the clip.
+    pub time: f32,
+    pub biquat: Biquaternion,
+}
+
+impl Keyframe {
+    #[inline]
+    pub fn new(time: f32, transform: Transform) -> Self {
+        Self {
+            time,
+            biquat: transform.biquat,
+        }
+    }
+}
+
+/// An animation of a [`Transform`], given by timed keyframes.
+///
+/// The keyframes are relative to the entity's parent, like [`Transform`] itself,
+/// so an animated door swings with the house it belongs to.
+/// With the `animation` feature, play it on an entity with an `AnimationPlayer`.
+#[derive(Debug, Clone, PartialEq, TypeUuid)]
+#[uuid = "e9953e96-a7c3-4c43-82eb-a705ad70788a"]
+pub struct AnimationClip {
+    keyframes: Vec<Keyframe>,
+    pub interpolation: KeyframeInterpolation,
+}
+
+impl AnimationClip {
+    /// Creates a clip from `keyframes`, which are sorted by time.
+    ///
+    /// `(left, right)` and `(-left, -right)` are the same isometry, but interpolating toward
+    /// the wrong one goes the long way around. So each keyframe is replaced by whichever of
+    /// the two is closer to the keyframe before it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keyframes` is empty, or if a keyframe's time is not finite.
+    pub fn new(mut keyframes: Vec<Keyframe>, interpolation: KeyframeInterpolation) -> Self {
+        assert!(
+            !keyframes.is_empty(),
+            "an animation clip needs at least one keyframe"
+        );
+        assert!(
+            keyframes.iter().all(|keyframe| keyframe.time.is_finite()),
+            "keyframe times must be finite"
+        );
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        for i in 1..keyframes.len() {
+            let previous = keyframes[i - 1].biquat;
+            let keyframe = &mut keyframes[i];
+            if biquat_dot(previous, keyframe.biquat) < 0. {
+                keyframe.biquat = -keyframe.biquat;
+            }
+        }
+        Self {
+            keyframes,
+            interpolation,
+        }
+    }
+
+    /// A loop once around the great circle leaving `start` along its local `direction`,
+    /// taking `duration` seconds at constant speed.
+    ///
+    /// This suits platforms and other objects that circle the universe forever;
+    /// play it with `repeat` set on the `AnimationPlayer`.
+    pub fn great_circle(start: Transform, direction: Vec3, duration: f32) -> Self {
+        let direction = direction.normalize();
+        // Three segments, since a slerp across half the circle would be ambiguous.
+        let keyframes = (0..=3)
+            .map(|i| {
+                let fraction = i as f32 / 3.;
+                let step =
+                    Transform::from_translation(direction * std::f32::consts::TAU * fraction);
+                Keyframe::new(duration * fraction, start * step)
+            })
+            .collect();
+        Self::new(keyframes, KeyframeInterpolation::Slerp)
+    }
+
+    /// The keyframes, sorted by time.
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    /// The time of the last keyframe.
+    pub fn duration(&self) -> f32 {
+        self.keyframes[self.keyframes.len() - 1].time
+    }
+
+    /// The [`Transform`] at `time`.
+    /// Before the first keyframe this is the first keyframe, and after the last, the last.
+    pub fn sample(&self, time: f32) -> Transform {
+        let keyframes = &self.keyframes;
+        // The index of the first keyframe after `time`.
+        let next = keyframes.iter().position(|keyframe| keyframe.time > time);
+        let i = match next {
+            Some(0) => {
+                return Transform {
+                    biquat: keyframes[0].biquat,
+                }
+            }
+            Some(next) => next - 1,
+            None => {
+                return Transform {
+                    biquat: keyframes[keyframes.len() - 1].biquat,
+                }
+            }
+        };
+        let (start, end) = (keyframes[i], keyframes[i + 1]);
+        let s = (time - start.time) / (end.time - start.time);
+
+        let biquat = match self.interpolation {
+            KeyframeInterpolation::Step => start.biquat,
+            KeyframeInterpolation::Slerp => start.biquat.slerp(end.biquat, s),
+            KeyframeInterpolation::Cubic => {
+                let control = |i: usize| {
+                    let before = keyframes[i.saturating_sub(1)].biquat;
+                    let after = keyframes[(i + 1).min(keyframes.len() - 1)].biquat;
+                    squad_control_point(before, keyframes[i].biquat, after)
+                };
+                let path = start.biquat.slerp(end.biquat, s);
+                let controls = control(i).slerp(control(i + 1), s);
+                path.slerp(controls, 2. * s * (1. - s))
+            }
+        };
+        Transform {
+            biquat: biquat.normalize(),
+        }
+    }
+}
+
+/// The inner control point of squad at `q`, given its neighbors:
+/// `q * exp(-(log(q⁻¹ * before) + log(q⁻¹ * after)) / 4)`.
+#[inline]
+fn squad_control_point(before: Biquaternion, q: Biquaternion, after: Biquaternion) -> Biquaternion {
+    let inverse = q.inverse();
+    let tangent = (inverse * before).log() + (inverse * after).log();
+    (q * Biquaternion::exp(tangent * -0.25)).normalize()
+}
+
+#[inline]
+fn biquat_dot(a: Biquaternion, b: Biquaternion) -> f32 {
+    a.left.dot(b.left) + a.right.dot(b.right)
+}
+
+#[cfg(feature = "animation")]
+pub use player::*;
+
+#[cfg(feature = "animation")]
+mod player {
+    use super::AnimationClip;
+    use crate::{components::Transform, TransformSystem};
+    use bevy_app::prelude::*;
+    use bevy_asset::{AddAsset, Assets, Handle};
+    use bevy_core::Time;
+    use bevy_ecs::{
+        schedule::ParallelSystemDescriptorCoercion,
+        system::{IntoSystem, Query, Res},
+    };
+
+    /// Plays an [`AnimationClip`] on the entity's [`Transform`].
+    ///
+    /// Since the [`Transform`] is relative to the entity's parent,
+    /// the animation moves with the parent, and the entity's children move with the animation.
+    #[derive(Debug, Clone)]
+    pub struct AnimationPlayer {
+        pub clip: Handle<AnimationClip>,
+        /// The current time in the clip, in seconds.
+        pub time: f32,
+        /// How fast time passes in the clip; 1 is real time.
+        pub speed: f32,
+        /// Whether to start over at the end of the clip, instead of stopping on the last keyframe.
+        pub repeat: bool,
+        pub paused: bool,
+    }
+
+    impl AnimationPlayer {
+        pub fn new(clip: Handle<AnimationClip>) -> Self {
+            Self {
+                clip,
+                time: 0.,
+                speed: 1.,
+                repeat: false,
+                paused: false,
+            }
+        }
+
+        /// Returns this player with [`AnimationPlayer::repeat`] set.
+        pub fn repeating(mut self) -> Self {
+            self.repeat = true;
+            self
+        }
+    }
+
+    /// Advances each unpaused [`AnimationPlayer`] and writes the clip's [`Transform`] for the new time.
+    pub fn animation_player_system(
+        time: Res<Time>,
+        clips: Res<Assets<AnimationClip>>,
+        mut query: Query<(&mut AnimationPlayer, &mut Transform)>,
+    ) {
+        for (mut player, mut transform) in query.iter_mut() {
+            if player.paused {
+                continue;
+            }
+            let clip = match clips.get(&player.clip) {
+                Some(clip) => clip,
+                None => continue,
+            };
+            let duration = clip.duration();
+            player.time += time.delta_seconds() * player.speed;
+            if player.repeat && duration > 0. {
+                player.time = player.time.rem_euclid(duration);
+            }
+            *transform = clip.sample(player.time);
+        }
+    }
+
+    /// Adds [`AnimationClip`] assets and the [`animation_player_system`].
+    /// Add it after `AssetPlugin` and [`TransformPlugin`](crate::TransformPlugin).
+    #[derive(Default)]
+    pub struct AnimationPlugin;
+
+    impl Plugin for AnimationPlugin {
+        fn build(&self, app: &mut AppBuilder) {
+            app.add_asset::<AnimationClip>().add_system_to_stage(
+                CoreStage::PostUpdate,
+                animation_player_system
+                    .system()
+                    .before(TransformSystem::TransformPropagate),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry;
+    use bevy_math::{Quat, Vec4};
+    use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+    #[test]
+    fn step_and_slerp() {
+        let a = Transform::from_translation(Vec3::new(0.3, 0.0, 0.0));
+        let b = a * Transform::from_translation(Vec3::new(0.0, 0.4, 0.0));
+        let keyframes = vec![Keyframe::new(2.0, b), Keyframe::new(0.0, a)];
+
+        let step = AnimationClip::new(keyframes.clone(), KeyframeInterpolation::Step);
+        assert_eq!(step.duration(), 2.0);
+        assert_eq!(step.sample(-1.0), a);
+        assert_eq!(step.sample(1.9), a);
+        assert_eq!(step.sample(2.0), b);
+        assert_eq!(step.sample(5.0), b);
+
+        let slerp = AnimationClip::new(keyframes, KeyframeInterpolation::Slerp);
+        let start = a * Vec4::W;
+        let end = b * Vec4::W;
+        let middle = slerp.sample(1.0) * Vec4::W;
+        assert!(middle.abs_diff_eq(geometry::midpoint(start, end), 1e-4));
+    }
+
+    #[test]
+    fn short_way_around() {
+        // The same isometry with the opposite sign is not a half turn.
+        let a = Transform::identity();
+        let b = Transform::from_rotation(Quat::from_rotation_y(0.4));
+        let clip = AnimationClip::new(
+            vec![
+                Keyframe::new(0.0, a),
+                Keyframe {
+                    time: 1.0,
+                    biquat: -b.biquat,
+                },
+            ],
+            KeyframeInterpolation::Slerp,
+        );
+        let middle = clip.sample(0.5);
+        let expected = Transform::from_rotation(Quat::from_rotation_y(0.2));
+        assert!(middle.biquat.abs_diff_eq(expected.biquat, 1e-4));
+    }
+
+    #[test]
+    fn great_circle_loop() {
+        let start = Transform::from_translation(Vec3::new(0.1, 0.2, 0.0))
+            * Transform::from_rotation(Quat::from_rotation_x(0.5));
+        let clip = AnimationClip::great_circle(start, Vec3::Z, 4.0);
+        let origin = start * Vec4::W;
+        for &(time, distance) in &[(0.0, 0.0), (1.0, FRAC_PI_2), (2.0, PI), (3.0, FRAC_PI_2)] {
+            let position = clip.sample(time) * Vec4::W;
+            assert!((geometry::distance(origin, position) - distance).abs() < 1e-3);
+            // Always along the same great circle, leaving along the local `z` axis.
+            let expected = start * Transform::from_translation(Vec3::Z * TAU * time / 4.0);
+            assert!(position.abs_diff_eq(expected * Vec4::W, 1e-4));
+        }
+        assert!((clip.sample(4.0) * Vec4::W).abs_diff_eq(origin, 1e-4));
+    }
+
+    #[test]
+    fn cubic_passes_through_keyframes() {
+        let keyframes: Vec<_> = [
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.5, 0.1, 0.0),
+            Vec3::new(0.6, 0.7, -0.2),
+            Vec3::new(0.2, 1.0, 0.3),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, &translation)| {
+            Keyframe::new(
+                i as f32,
+                Transform::from_translation(translation)
+                    * Transform::from_rotation(Quat::from_rotation_z(i as f32 * 0.3)),
+            )
+        })
+        .collect();
+        let clip = AnimationClip::new(keyframes.clone(), KeyframeInterpolation::Cubic);
+        for keyframe in &keyframes {
+            assert!(clip
+                .sample(keyframe.time)
+                .biquat
+                .abs_diff_eq(keyframe.biquat, 1e-4));
+        }
+
+        // The velocity matches on both sides of an inner keyframe.
+        let h = 1e-2;
+        let velocity = |a: f32, b: f32| {
+            (clip.sample(b).compute_matrix() - clip.sample(a).compute_matrix()) * (1. / h)
+        };
+        assert!(velocity(1.0 - h, 1.0).abs_diff_eq(velocity(1.0, 1.0 + h), 2e-2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn nan_time_is_rejected() {
+        let a = Transform::from_translation(Vec3::new(0.3, 0.0, 0.0));
+        AnimationClip::new(
+            vec![
+                Keyframe::new(f32::NAN, a),
+                Keyframe::new(1.0, a),
+                Keyframe::new(0.0, a),
+            ],
+            KeyframeInterpolation::Slerp,
+        );
+    }
+
+    #[cfg(feature = "animation")]
+    #[test]
+    fn player_follows_time() {
+        use bevy_asset::Assets;
+        use bevy_core::Time;
+        use bevy_ecs::{
+            schedule::{Stage, SystemStage},
+            system::IntoSystem,
+        };
+        use std::{thread, time::Duration};
+
+        let mut app = bevy_app::App::build();
+        app.add_plugin(bevy_core::CorePlugin::default())
+            .add_plugin(bevy_asset::AssetPlugin::default())
+            .add_plugin(AnimationPlugin);
+        let world = app.world_mut();
+
+        let a = Transform::from_translation(Vec3::new(0.3, 0.0, 0.0));
+        let b = a * Transform::from_translation(Vec3::new(0.0, 0.4, 0.0));
+        let clip = AnimationClip::new(
+            vec![Keyframe::new(0.0, a), Keyframe::new(0.05, b)],
+            KeyframeInterpolation::Slerp,
+        );
+        let handle = world
+            .get_resource_mut::<Assets<AnimationClip>>()
+            .unwrap()
+            .add(clip.clone());
+        let once = world
+            .spawn()
+            .insert_bundle((AnimationPlayer::new(handle.clone()), Transform::identity()))
+            .id();
+        let repeating = world
+            .spawn()
+            .insert_bundle((
+                AnimationPlayer {
+                    speed: 10.0,
+                    ..AnimationPlayer::new(handle).repeating()
+                },
+                Transform::identity(),
+            ))
+            .id();
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(animation_player_system.system());
+
+        // Before any time passes, the players sit on the first keyframe.
+        world.insert_resource(Time::default());
+        stage.run(world);
+        assert_eq!(*world.get::<Transform>(once).unwrap(), a);
+
+        let mut time = world.get_resource_mut::<Time>().unwrap();
+        time.update();
+        thread::sleep(Duration::from_millis(20));
+        time.update();
+        let delta = time.delta_seconds();
+        stage.run(world);
+
+        let player = world.get::<AnimationPlayer>(once).unwrap();
+        assert_eq!(player.time, delta);
+        assert_eq!(
+            *world.get::<Transform>(once).unwrap(),
+            clip.sample(player.time)
+        );
+        // Ten times as fast, and around again from the start.
+        let player = world.get::<AnimationPlayer>(repeating).unwrap();
+        assert!((player.time - (delta * 10.0).rem_euclid(0.05)).abs() < 1e-6);
+        assert!(player.time < 0.05);
+
+        // Paused players stay put.
+        world.get_mut::<AnimationPlayer>(once).unwrap().paused = true;
+        stage.run(world);
+        assert_eq!(world.get::<AnimationPlayer>(once).unwrap().time, delta);
+    }
+}
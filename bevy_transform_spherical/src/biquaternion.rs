@@ -1,12 +1,36 @@
 use std::ops::*;
 
+#[cfg(feature = "serialize")]
+use bevy_math::Vec3;
 use bevy_math::{Mat4, Quat, Vec4};
 use bevy_reflect::Reflect;
+#[cfg(feature = "serialize")]
+use bevy_reflect::ReflectDeserialize;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+use crate::bivector::{quat_exp, quat_log, Bivector};
+#[cfg(feature = "serialize")]
+use crate::components::Transform;
 
 /// A pair of quaternions, representing a 4D rotation.
 /// This is the rotation taking the vector `v`
 /// to `Vec4::from(left * Quat::from(v) * right)`.
+///
+/// With the `serialize` feature, it is written by `serde`, and so in scene files,
+/// as the translation and rotation of the equivalent
+/// [`Transform`](crate::components::Transform), as returned by
+/// [`Transform::to_translation_rotation`](crate::components::Transform::to_translation_rotation):
+/// `(translation: (0.0, 0.0, 0.5), rotation: (0.0, 0.0, 0.0, 1.0))`.
+/// Either field may be left out, and defaults to the identity.
+/// The raw form `(left: (..), right: (..))` is also accepted.
 #[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(Serialize, Deserialize),
+    reflect_value(PartialEq, Serialize, Deserialize),
+    serde(into = "ReadableBiquaternion", from = "BiquaternionRepr")
+)]
 pub struct Biquaternion {
     pub left: Quat,
     pub right: Quat,
@@ -18,6 +42,10 @@ impl Biquaternion {
         right: Quat::IDENTITY,
     };
 
+    /// Components of `left` at most this far from zero are skipped when choosing
+    /// the sign in [`Biquaternion::canonicalize`], so that rounding error does not flip it.
+    pub const CANONICAL_EPSILON: f32 = 1e-4;
+
     #[inline(always)]
     pub fn from_rotation(q: Quat) -> Self {
         Self {
@@ -26,6 +54,164 @@ impl Biquaternion {
         }
     }
 
+    /// Creates a rotation by `angle` in the XY plane, turning `Vec4::X` toward `Vec4::Y`.
+    /// This is a rotation around the z axis, fixing `Vec4::W`.
+    #[inline]
+    pub fn from_rotation_in_xy(angle: f32) -> Self {
+        Self::exp(Bivector {
+            xy: angle,
+            ..Bivector::ZERO
+        })
+    }
+
+    /// Creates a rotation by `angle` in the XZ plane, turning `Vec4::X` toward `Vec4::Z`.
+    /// This is a rotation around the y axis, fixing `Vec4::W`.
+    #[inline]
+    pub fn from_rotation_in_xz(angle: f32) -> Self {
+        Self::exp(Bivector {
+            xz: angle,
+            ..Bivector::ZERO
+        })
+    }
+
+    /// Creates a rotation by `angle` in the YZ plane, turning `Vec4::Y` toward `Vec4::Z`.
+    /// This is a rotation around the x axis, fixing `Vec4::W`.
+    #[inline]
+    pub fn from_rotation_in_yz(angle: f32) -> Self {
+        Self::exp(Bivector {
+            yz: angle,
+            ..Bivector::ZERO
+        })
+    }
+
+    /// Creates a rotation by `angle` in the XW plane, turning `Vec4::X` toward `Vec4::W`.
+    /// This is a translation by `-angle` along the x axis.
+    #[inline]
+    pub fn from_rotation_in_xw(angle: f32) -> Self {
+        Self::exp(Bivector {
+            xw: angle,
+            ..Bivector::ZERO
+        })
+    }
+
+    /// Creates a rotation by `angle` in the YW plane, turning `Vec4::Y` toward `Vec4::W`.
+    /// This is a translation by `-angle` along the y axis.
+    #[inline]
+    pub fn from_rotation_in_yw(angle: f32) -> Self {
+        Self::exp(Bivector {
+            yw: angle,
+            ..Bivector::ZERO
+        })
+    }
+
+    /// Creates a rotation by `angle` in the ZW plane, turning `Vec4::Z` toward `Vec4::W`.
+    /// This is a translation by `-angle` along the z axis.
+    #[inline]
+    pub fn from_rotation_in_zw(angle: f32) -> Self {
+        Self::exp(Bivector {
+            zw: angle,
+            ..Bivector::ZERO
+        })
+    }
+
+    /// Creates a rotation by `angle` in the plane spanned by `u` and `v`, turning `u` toward `v`.
+    /// The orthogonal complement of the plane is fixed.
+    ///
+    /// `u` and `v` need not be orthonormal, but they must be linearly independent.
+    /// If they are not, this returns the identity.
+    #[inline]
+    pub fn from_rotation_in_plane(u: Vec4, v: Vec4, angle: f32) -> Self {
+        let u = u.normalize_or_zero();
+        let v = (v - u * u.dot(v)).normalize_or_zero();
+        Self::exp(Bivector::wedge(u, v) * angle)
+    }
+
+    /// Creates the rotation with the given 4x4 matrix, the inverse of `Mat4::from`.
+    ///
+    /// This uses Van Elfrinkhof's factorization: the matrix is written in the basis of
+    /// the sixteen maps `v ↦ eₐ * v * e_b`, where the `e`s are the quaternion units.
+    /// For a rotation, the coefficients form the rank one matrix `left * rightᵀ`.
+    ///
+    /// If `matrix` is not quite orthogonal, because of rounding or because it came from another tool,
+    /// this returns the nearest rotation in the Frobenius norm, found by power iteration,
+    /// which stops once the rotation stops changing.
+    /// A reflection is also projected onto a rotation, though not a meaningful one.
+    /// If `matrix` is zero, or not finite, or has no part that is a rotation,
+    /// this returns [`Biquaternion::IDENTITY`].
+    pub fn from_mat4(matrix: Mat4) -> Self {
+        const MAX_ITERATIONS: usize = 64;
+        const TOLERANCE: f32 = 1e-7;
+
+        let units = [Vec4::X, Vec4::Y, Vec4::Z, Vec4::W];
+
+        // The coefficient of `v ↦ eₐ * v * e_b` is `left[a] * right[b]`.
+        // The sixteen maps are orthogonal, each with squared norm 4.
+        let mut associate = [Vec4::ZERO; 4];
+        for (b, &e_b) in units.iter().enumerate() {
+            for (a, &e_a) in units.iter().enumerate() {
+                let mut sum = 0.;
+                for (j, &e_j) in units.iter().enumerate() {
+                    let image = Quat::from(e_a) * Quat::from(e_j) * Quat::from(e_b);
+                    sum += matrix.col(j).dot(Vec4::from(image));
+                }
+                associate[b][a] = sum / 4.;
+            }
+        }
+        let associate = Mat4::from_cols(associate[0], associate[1], associate[2], associate[3]);
+        let transpose = associate.transpose();
+
+        // The nearest rotation comes from the leading singular vectors.
+        // Start the power iteration from the largest row, which is exact for a true rotation.
+        let start = units
+            .iter()
+            .map(|&e| transpose * e)
+            .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+            .unwrap();
+        if !(start.length_squared() > 1e-12 && start.length_squared().is_finite()) {
+            return Self::IDENTITY;
+        }
+        let mut right = start.normalize();
+        for _ in 0..MAX_ITERATIONS {
+            let next = (transpose * (associate * right)).normalize_or_zero();
+            let converged = next.abs_diff_eq(right, TOLERANCE);
+            right = next;
+            if converged {
+                break;
+            }
+        }
+        let left = (associate * right).normalize_or_zero();
+        if left == Vec4::ZERO || right == Vec4::ZERO {
+            return Self::IDENTITY;
+        }
+
+        Self {
+            left: Quat::from(left),
+            right: Quat::from(right),
+        }
+    }
+
+    /// The exponential map from so(4) to the group of rotations.
+    /// Rotates at the rates given by `bivector` for one unit of time.
+    #[inline]
+    pub fn exp(bivector: Bivector) -> Self {
+        let (left, right) = bivector.to_left_right();
+        Self {
+            left: quat_exp(left),
+            right: quat_exp(right),
+        }
+    }
+
+    /// The logarithm of a normalized biquaternion; the inverse of [`Biquaternion::exp`].
+    ///
+    /// The bivector is recovered exactly when its left and right halves
+    /// (see [`Bivector`]) both have length less than π.
+    /// Outside that range, there is more than one bivector with the same exponential,
+    /// and this returns one of them.
+    #[inline]
+    pub fn log(self) -> Bivector {
+        Bivector::from_left_right(quat_log(self.left), quat_log(self.right))
+    }
+
     /// Returns the biquaternion conjugate of `self`. For a unit biquaternion the
     /// conjugate is also the inverse.
     #[inline(always)]
@@ -91,13 +277,48 @@ impl Biquaternion {
         self.left.is_normalized() && self.right.is_normalized()
     }
 
+    /// How far `self` is from being normalized:
+    /// the larger of the distances of the lengths of the two quaternions from 1.
+    #[inline(always)]
+    pub fn drift(self) -> f32 {
+        (self.left.length() - 1.)
+            .abs()
+            .max((self.right.length() - 1.).abs())
+    }
+
     /// Returns true if the absolute difference of all elements between `self` and `other`
     /// is less than or equal to `max_abs_diff`.
+    ///
+    /// Like `==`, this says that `self` and `-self` differ, although they are the same rotation.
+    /// Use [`Biquaternion::approx_same_rotation`] to compare rotations.
     #[inline(always)]
     pub fn abs_diff_eq(self, other: Self, max_abs_diff: f32) -> bool {
         self.left.abs_diff_eq(other.left, max_abs_diff)
             && self.right.abs_diff_eq(other.right, max_abs_diff)
     }
+
+    /// Returns true if `self` and `other` are approximately the same rotation:
+    /// if either `other` or `-other` is within `max_abs_diff` of `self`, elementwise.
+    #[inline]
+    pub fn approx_same_rotation(self, other: Self, max_abs_diff: f32) -> bool {
+        self.abs_diff_eq(other, max_abs_diff) || self.abs_diff_eq(-other, max_abs_diff)
+    }
+
+    /// Returns whichever of `self` and `-self`, which are the same rotation, is canonical:
+    /// the one in which the first component of `left`, in the order `w, x, y, z`,
+    /// that is further than [`Biquaternion::CANONICAL_EPSILON`] from zero is positive.
+    ///
+    /// So the same rotation always has the same canonical form, and nearby rotations have
+    /// nearby canonical forms, unless that component is itself close to the threshold.
+    #[inline]
+    pub fn canonicalize(self) -> Self {
+        let left = self.left;
+        let sign = [left.w, left.x, left.y, left.z]
+            .iter()
+            .find(|c| c.abs() > Self::CANONICAL_EPSILON)
+            .map_or(1., |c| c.signum());
+        self * sign
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -110,6 +331,56 @@ impl From<Biquaternion> for Mat4 {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// The form in which a [`Biquaternion`] is serialized.
+/// The translation is measured in radians of the unit sphere, not in world units.
+#[cfg(feature = "serialize")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ReadableBiquaternion {
+    translation: Vec3,
+    rotation: Quat,
+}
+
+/// The forms from which a [`Biquaternion`] can be deserialized.
+/// The quaternions of either form are normalized, as hand-edited ones rarely have unit length.
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BiquaternionRepr {
+    Readable(ReadableBiquaternion),
+    Raw { left: Quat, right: Quat },
+}
+
+#[cfg(feature = "serialize")]
+impl From<Biquaternion> for ReadableBiquaternion {
+    fn from(biquat: Biquaternion) -> Self {
+        let (translation, rotation) = Transform { biquat }.to_translation_rotation();
+        Self {
+            translation,
+            rotation,
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl From<BiquaternionRepr> for Biquaternion {
+    fn from(repr: BiquaternionRepr) -> Self {
+        match repr {
+            BiquaternionRepr::Readable(ReadableBiquaternion {
+                translation,
+                rotation,
+            }) => {
+                (Transform::from_translation(translation)
+                    * Transform::from_rotation(rotation.normalize()))
+                .biquat
+            }
+            BiquaternionRepr::Raw { left, right } => Biquaternion { left, right }.normalize(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 impl Default for Biquaternion {
     fn default() -> Self {
         Self::IDENTITY
@@ -206,12 +477,6 @@ from_rotation_arc
 from_rotation_arc_colinear
 
 
-// Maybe explicitly say from_rotation_around_xy vs from_rotation_in_xy?
-from_rotation_x
-from_rotation_y
-from_rotation_z
-
-
 Trait Implementations
 
 Display
@@ -221,3 +486,175 @@ Product<&'a Quat>
 */
 
 // TODO: Method docs
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::components::Transform;
+    use bevy_math::Vec3;
+
+    #[test]
+    fn plane_rotations() {
+        let angle = 0.6;
+        for (rotation, u, v) in [
+            (Biquaternion::from_rotation_in_xy(angle), Vec4::X, Vec4::Y),
+            (Biquaternion::from_rotation_in_xz(angle), Vec4::X, Vec4::Z),
+            (Biquaternion::from_rotation_in_yz(angle), Vec4::Y, Vec4::Z),
+            (Biquaternion::from_rotation_in_xw(angle), Vec4::X, Vec4::W),
+            (Biquaternion::from_rotation_in_yw(angle), Vec4::Y, Vec4::W),
+            (Biquaternion::from_rotation_in_zw(angle), Vec4::Z, Vec4::W),
+        ] {
+            let (sin, cos) = angle.sin_cos();
+            assert!((rotation * u).abs_diff_eq(u * cos + v * sin, 1e-6));
+            assert!((rotation * v).abs_diff_eq(v * cos - u * sin, 1e-6));
+            assert!(rotation.abs_diff_eq(Biquaternion::from_rotation_in_plane(u, v, angle), 1e-6));
+        }
+    }
+
+    #[test]
+    fn plane_rotation_fixes_complement() {
+        let u = Vec4::new(1.0, 2.0, 0.0, 1.0);
+        let v = Vec4::new(0.0, 1.0, 1.0, 3.0);
+        let rotation = Biquaternion::from_rotation_in_plane(u, v, 1.1);
+
+        let (u, v) = (u.normalize(), v.normalize());
+        for axis in [Vec4::X, Vec4::Y, Vec4::Z, Vec4::W] {
+            // Project out the plane, leaving a vector in its orthogonal complement.
+            let w = (v - u * u.dot(v)).normalize();
+            let fixed = axis - u * u.dot(axis) - w * w.dot(axis);
+            assert!((rotation * fixed).abs_diff_eq(fixed, 1e-5));
+        }
+    }
+
+    #[test]
+    fn plane_rotation_through_w_is_translation() {
+        assert!(Biquaternion::from_rotation_in_plane(Vec4::W, Vec4::Y, 0.8)
+            .abs_diff_eq(Transform::from_translation(Vec3::Y * 0.8).biquat, 1e-6));
+    }
+
+    #[test]
+    fn from_mat4_round_trip() {
+        let biquat = Transform::from_translation(Vec3::new(0.4, -1.3, 2.0)).biquat
+            * Biquaternion::from_rotation(Quat::from_axis_angle(
+                Vec3::new(2.0, 1.0, -1.0).normalize(),
+                2.5,
+            ))
+            * Biquaternion::from_rotation_in_zw(1.0);
+        let matrix = Mat4::from(biquat);
+        let round_trip = Biquaternion::from_mat4(matrix);
+        assert!(Mat4::from(round_trip).abs_diff_eq(matrix, 1e-5));
+        assert!(round_trip.is_normalized());
+
+        assert!(
+            Biquaternion::from_mat4(Mat4::IDENTITY).abs_diff_eq(Biquaternion::IDENTITY, 1e-6)
+                || Biquaternion::from_mat4(Mat4::IDENTITY)
+                    .abs_diff_eq(-Biquaternion::IDENTITY, 1e-6)
+        );
+    }
+
+    #[test]
+    fn from_mat4_projects() {
+        let biquat = Transform::from_translation(Vec3::new(-0.5, 0.2, 0.7)).biquat
+            * Biquaternion::from_rotation(Quat::from_rotation_x(0.9));
+        let matrix = Mat4::from(biquat);
+        let noisy = matrix * 1.01
+            + Mat4::from_cols(
+                Vec4::new(0.01, -0.02, 0.0, 0.01),
+                Vec4::new(0.0, 0.02, 0.01, -0.01),
+                Vec4::new(-0.01, 0.0, 0.02, 0.0),
+                Vec4::new(0.02, 0.01, 0.0, -0.02),
+            );
+        let projected = Biquaternion::from_mat4(noisy);
+        assert!(projected.is_normalized());
+        assert!(Mat4::from(projected).abs_diff_eq(matrix, 0.05));
+    }
+
+    #[test]
+    fn from_mat4_degenerate() {
+        assert_eq!(Biquaternion::from_mat4(Mat4::ZERO), Biquaternion::IDENTITY);
+        let nan = Mat4::from_cols(Vec4::splat(f32::NAN), Vec4::X, Vec4::Y, Vec4::Z);
+        assert_eq!(Biquaternion::from_mat4(nan), Biquaternion::IDENTITY);
+
+        // With a column missing, the rest still picks out the rotation.
+        let biquat = Transform::from_translation(Vec3::new(0.4, -0.3, 0.1)).biquat
+            * Biquaternion::from_rotation(Quat::from_rotation_z(1.3));
+        let matrix = Mat4::from(biquat);
+        let rank_three = Mat4::from_cols(matrix.x_axis, matrix.y_axis, matrix.z_axis, Vec4::ZERO);
+        let projected = Biquaternion::from_mat4(rank_three);
+        assert!(projected.is_normalized());
+        assert!(projected.approx_same_rotation(biquat, 1e-4));
+
+        // A rank one matrix has no rotation to find, but still gives one.
+        let rank_one = Mat4::from_cols(Vec4::X, Vec4::ZERO, Vec4::ZERO, Vec4::ZERO);
+        assert!(Biquaternion::from_mat4(rank_one).is_normalized());
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn ron_round_trip() {
+        let transform = Transform::from_translation(Vec3::new(0.3, -0.2, 1.1))
+            * Transform::from_rotation(Quat::from_rotation_y(0.7));
+        let text = ron::to_string(&transform.biquat).unwrap();
+        assert!(text.contains("translation"), "{}", text);
+        let round_trip: Biquaternion = ron::from_str(&text).unwrap();
+        assert!(round_trip.abs_diff_eq(transform.biquat, 1e-5));
+
+        // Either field may be omitted.
+        let translated: Biquaternion = ron::from_str("(translation: (0.0, 0.5, 0.0))").unwrap();
+        assert!(translated.abs_diff_eq(Transform::from_translation(Vec3::Y * 0.5).biquat, 1e-6));
+
+        // The raw quaternions are accepted too, but unknown fields are not.
+        let raw = format!(
+            "(left: {}, right: {})",
+            ron::to_string(&transform.biquat.left).unwrap(),
+            ron::to_string(&transform.biquat.right).unwrap()
+        );
+        assert!(ron::from_str::<Biquaternion>(&raw)
+            .unwrap()
+            .abs_diff_eq(transform.biquat, 1e-6));
+        assert!(
+            ron::from_str::<Biquaternion>("(translation: (0.0, 0.5, 0.0), scale: 2.0)").is_err()
+        );
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn raw_repr_is_normalized() {
+        let raw: Biquaternion =
+            ron::from_str("(left: (0.0, 0.0, 0.0, 2.0), right: (0.0, 3.0, 0.0, 0.0))").unwrap();
+        assert!(raw.is_normalized());
+        assert!(raw.abs_diff_eq(
+            Biquaternion {
+                left: Quat::IDENTITY,
+                right: Quat::from_xyzw(0.0, 1.0, 0.0, 0.0),
+            },
+            1e-6
+        ));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn reflect_round_trip() {
+        use bevy_reflect::{
+            serde::{ReflectDeserializer, ReflectSerializer},
+            TypeRegistry,
+        };
+        use serde::de::DeserializeSeed;
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Biquaternion>();
+        registry.register::<Transform>();
+
+        let transform = Transform::from_translation(Vec3::new(-1.0, 0.4, 0.2))
+            * Transform::from_rotation(Quat::from_rotation_x(-1.2));
+        let text = ron::to_string(&ReflectSerializer::new(&transform, &registry)).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&text).unwrap();
+        let dynamic = ReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+
+        let mut round_trip = Transform::identity();
+        round_trip.apply(&*dynamic);
+        assert!(round_trip.biquat.abs_diff_eq(transform.biquat, 1e-5));
+    }
+}
@@ -0,0 +1,274 @@
+use std::ops::*;
+
+use bevy_math::{Quat, Vec3, Vec4};
+use bevy_reflect::Reflect;
+
+/// An element of the Lie algebra so(4); an infinitesimal rotation of 4D space.
+///
+/// Each component is the rate of rotation in one coordinate plane.
+/// The component `ij` is the rate at which the `i` axis turns toward the `j` axis,
+/// so a positive `xy` turns `Vec4::X` toward `Vec4::Y`,
+/// and a positive `xw` turns `Vec4::X` toward `Vec4::W`.
+///
+/// Use [`Biquaternion::exp`](crate::biquaternion::Biquaternion::exp) to turn a bivector
+/// into a finite rotation, and [`Biquaternion::log`](crate::biquaternion::Biquaternion::log)
+/// to go back.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect)]
+pub struct Bivector {
+    pub xy: f32,
+    pub xz: f32,
+    pub yz: f32,
+    pub xw: f32,
+    pub yw: f32,
+    pub zw: f32,
+}
+
+impl Bivector {
+    pub const ZERO: Self = Self {
+        xy: 0.,
+        xz: 0.,
+        yz: 0.,
+        xw: 0.,
+        yw: 0.,
+        zw: 0.,
+    };
+
+    #[inline(always)]
+    pub fn new(xy: f32, xz: f32, yz: f32, xw: f32, yw: f32, zw: f32) -> Self {
+        Self {
+            xy,
+            xz,
+            yz,
+            xw,
+            yw,
+            zw,
+        }
+    }
+
+    /// The bivector whose exponential is [`Transform::from_translation(translation)`](crate::components::Transform::from_translation).
+    /// Translating along an axis rotates `Vec4::W` toward that axis.
+    #[inline]
+    pub fn from_translation(translation: Vec3) -> Self {
+        Self {
+            xw: -translation.x,
+            yw: -translation.y,
+            zw: -translation.z,
+            ..Self::ZERO
+        }
+    }
+
+    /// The bivector whose exponential rotates around the axis `scaled_axis`,
+    /// by an angle equal to its length. This rotation fixes `Vec4::W`.
+    #[inline]
+    pub fn from_scaled_axis(scaled_axis: Vec3) -> Self {
+        Self {
+            xy: scaled_axis.z,
+            xz: -scaled_axis.y,
+            yz: scaled_axis.x,
+            ..Self::ZERO
+        }
+    }
+
+    /// The wedge product `u ∧ v`.
+    /// If `u` and `v` are orthonormal, this is the unit rotation turning `u` toward `v`.
+    #[inline]
+    pub fn wedge(u: Vec4, v: Vec4) -> Self {
+        Self {
+            xy: u.x * v.y - u.y * v.x,
+            xz: u.x * v.z - u.z * v.x,
+            yz: u.y * v.z - u.z * v.y,
+            xw: u.x * v.w - u.w * v.x,
+            yw: u.y * v.w - u.w * v.y,
+            zw: u.z * v.w - u.w * v.z,
+        }
+    }
+
+    /// Splits `self` into the generators of its left and right quaternions.
+    ///
+    /// so(4) is the direct sum of two copies of so(3), acting by
+    /// left and right multiplication respectively.
+    #[inline]
+    pub(crate) fn to_left_right(self) -> (Vec3, Vec3) {
+        (
+            0.5 * Vec3::new(self.yz - self.xw, -self.xz - self.yw, self.xy - self.zw),
+            0.5 * Vec3::new(-self.yz - self.xw, self.xz - self.yw, -self.xy - self.zw),
+        )
+    }
+
+    /// The inverse of [`Bivector::to_left_right`].
+    #[inline]
+    pub(crate) fn from_left_right(left: Vec3, right: Vec3) -> Self {
+        Self {
+            xy: left.z - right.z,
+            xz: right.y - left.y,
+            yz: left.x - right.x,
+            xw: -(left.x + right.x),
+            yw: -(left.y + right.y),
+            zw: -(left.z + right.z),
+        }
+    }
+
+    /// Returns true if the absolute difference of all elements between `self` and `other`
+    /// is less than or equal to `max_abs_diff`.
+    #[inline]
+    pub fn abs_diff_eq(self, other: Self, max_abs_diff: f32) -> bool {
+        (self.xy - other.xy).abs() <= max_abs_diff
+            && (self.xz - other.xz).abs() <= max_abs_diff
+            && (self.yz - other.yz).abs() <= max_abs_diff
+            && (self.xw - other.xw).abs() <= max_abs_diff
+            && (self.yw - other.yw).abs() <= max_abs_diff
+            && (self.zw - other.zw).abs() <= max_abs_diff
+    }
+}
+
+/// The exponential of the pure quaternion `v`.
+pub(crate) fn quat_exp(v: Vec3) -> Quat {
+    let angle = v.length();
+    let sin_by_angle = if angle < 0.0001 {
+        1. - angle * angle / 6.
+    } else {
+        angle.sin() / angle
+    };
+    let v = v * sin_by_angle;
+    Quat::from_xyzw(v.x, v.y, v.z, angle.cos())
+}
+
+/// The logarithm of the unit quaternion `q`; the inverse of [`quat_exp`].
+/// The result has length at most π.
+pub(crate) fn quat_log(q: Quat) -> Vec3 {
+    let v = Vec3::new(q.x, q.y, q.z);
+    let sin = v.length();
+    if sin < 0.0001 && q.w > 0. {
+        v / q.w
+    } else if sin > 0. {
+        v * (sin.atan2(q.w) / sin)
+    } else {
+        // `q` is -1, so any axis will do.
+        Vec3::X * std::f32::consts::PI
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl Add for Bivector {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            xy: self.xy + rhs.xy,
+            xz: self.xz + rhs.xz,
+            yz: self.yz + rhs.yz,
+            xw: self.xw + rhs.xw,
+            yw: self.yw + rhs.yw,
+            zw: self.zw + rhs.zw,
+        }
+    }
+}
+
+impl Sub for Bivector {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            xy: self.xy - rhs.xy,
+            xz: self.xz - rhs.xz,
+            yz: self.yz - rhs.yz,
+            xw: self.xw - rhs.xw,
+            yw: self.yw - rhs.yw,
+            zw: self.zw - rhs.zw,
+        }
+    }
+}
+
+impl Neg for Bivector {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            xy: -self.xy,
+            xz: -self.xz,
+            yz: -self.yz,
+            xw: -self.xw,
+            yw: -self.yw,
+            zw: -self.zw,
+        }
+    }
+}
+
+impl Mul<f32> for Bivector {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self {
+        Self {
+            xy: self.xy * rhs,
+            xz: self.xz * rhs,
+            yz: self.yz * rhs,
+            xw: self.xw * rhs,
+            yw: self.yw * rhs,
+            zw: self.zw * rhs,
+        }
+    }
+}
+
+impl Div<f32> for Bivector {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self {
+        Self {
+            xy: self.xy / rhs,
+            xz: self.xz / rhs,
+            yz: self.yz / rhs,
+            xw: self.xw / rhs,
+            yw: self.yw / rhs,
+            zw: self.zw / rhs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{biquaternion::Biquaternion, components::Transform};
+
+    #[test]
+    fn exp_log_round_trip() {
+        for bivector in [
+            Bivector::ZERO,
+            Bivector::new(0.1, 0.2, 0.3, 0.4, 0.5, 0.6),
+            Bivector::new(-1.5, 0.25, 2.0, 0.0, -0.75, 1.0),
+            Bivector::from_translation(Vec3::new(1.0, -2.0, 0.5)),
+            Bivector::from_scaled_axis(Vec3::new(0.0, 3.0, 0.0)),
+        ] {
+            let round_trip = Biquaternion::exp(bivector).log();
+            assert!(
+                round_trip.abs_diff_eq(bivector, 1e-4),
+                "{:?} != {:?}",
+                round_trip,
+                bivector
+            );
+        }
+    }
+
+    #[test]
+    fn exp_matches_constructors() {
+        let translation = Vec3::new(0.3, -1.2, 2.5);
+        assert!(Biquaternion::exp(Bivector::from_translation(translation))
+            .abs_diff_eq(Transform::from_translation(translation).biquat, 1e-6));
+
+        let rotation = Quat::from_axis_angle(Vec3::new(1.0, 2.0, 2.0).normalize(), 0.7);
+        assert!(Biquaternion::exp(Bivector::from_scaled_axis(
+            Vec3::new(1.0, 2.0, 2.0) / 3.0 * 0.7
+        ))
+        .abs_diff_eq(Biquaternion::from_rotation(rotation), 1e-6));
+    }
+
+    #[test]
+    fn exp_rotates_in_plane() {
+        let rotation = Biquaternion::exp(Bivector {
+            xw: 0.5,
+            ..Bivector::ZERO
+        });
+        let expected = Vec4::X * 0.5f32.cos() + Vec4::W * 0.5f32.sin();
+        assert!((rotation * Vec4::X).abs_diff_eq(expected, 1e-6));
+    }
+}
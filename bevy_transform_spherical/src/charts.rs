@@ -0,0 +1,307 @@
+use bevy_math::{Mat3, Vec3, Vec4};
+
+/// A coordinate chart on the unit 3-sphere: a way of naming points with three numbers.
+///
+/// Points of the sphere are unit `Vec4`s, as returned by
+/// [`GlobalTransform::position`](crate::components::GlobalTransform::position).
+/// Each chart covers all of the sphere except a small set where it is undefined or singular,
+/// which is documented on the chart.
+pub trait Chart {
+    /// The point of the sphere with the given coordinates.
+    fn to_sphere(coords: Vec3) -> Vec4;
+
+    /// The coordinates of `point`. The inverse of [`Chart::to_sphere`].
+    fn from_sphere(point: Vec4) -> Vec3;
+
+    /// The partial derivatives of [`Chart::to_sphere`] at `coords`, one per coordinate.
+    ///
+    /// Each column is tangent to the sphere at `to_sphere(coords)`.
+    fn jacobian(coords: Vec3) -> [Vec4; 3];
+
+    /// The derivative of [`Chart::from_sphere`] at `point`, as one gradient per coordinate.
+    ///
+    /// The gradients are tangent to the sphere, so `inverse_jacobian(p)[i].dot(v)`
+    /// is the rate of change of coordinate `i` along the tangent vector `v`.
+    ///
+    /// By default, this is the pseudo-inverse of [`Chart::jacobian`].
+    /// At a singular point of the chart, it is zero.
+    fn inverse_jacobian(point: Vec4) -> [Vec4; 3] {
+        let columns = Self::jacobian(Self::from_sphere(point));
+        let gram = Mat3::from_cols(
+            Vec3::new(
+                columns[0].dot(columns[0]),
+                columns[1].dot(columns[0]),
+                columns[2].dot(columns[0]),
+            ),
+            Vec3::new(
+                columns[0].dot(columns[1]),
+                columns[1].dot(columns[1]),
+                columns[2].dot(columns[1]),
+            ),
+            Vec3::new(
+                columns[0].dot(columns[2]),
+                columns[1].dot(columns[2]),
+                columns[2].dot(columns[2]),
+            ),
+        );
+        if gram.determinant().abs() < 1e-12 {
+            return [Vec4::ZERO; 3];
+        }
+        // The Gram matrix is symmetric, so its inverse's columns are also its rows.
+        let inverse = gram.inverse();
+        let row = |i: usize| {
+            let weights = inverse.col(i);
+            columns[0] * weights.x + columns[1] * weights.y + columns[2] * weights.z
+        };
+        [row(0), row(1), row(2)]
+    }
+
+    /// Converts the tangent vector `tangent` at `point` into a rate of change of coordinates.
+    #[inline]
+    fn tangent_to_coords(point: Vec4, tangent: Vec4) -> Vec3 {
+        let rows = Self::inverse_jacobian(point);
+        Vec3::new(
+            rows[0].dot(tangent),
+            rows[1].dot(tangent),
+            rows[2].dot(tangent),
+        )
+    }
+
+    /// Converts a rate of change of coordinates at `coords` into a tangent vector.
+    #[inline]
+    fn coords_to_tangent(coords: Vec3, velocity: Vec3) -> Vec4 {
+        let columns = Self::jacobian(coords);
+        columns[0] * velocity.x + columns[1] * velocity.y + columns[2] * velocity.z
+    }
+}
+
+/// The gnomonic chart, centered on `Vec4::W`: `(x, y, z)` names the point in the direction of `(x, y, z, 1)`.
+///
+/// Geodesics are straight lines in this chart, which is why it is the one used for mesh vertices;
+/// `pbr.vert` reads each vertex position as `(x, y, z, 1)`, after dividing by the
+/// [`UniverseRadius`](crate::universe::UniverseRadius).
+/// It covers the open hemisphere `w > 0`, and distances near the origin match `Transform::from_translation`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Gnomonic;
+
+impl Chart for Gnomonic {
+    #[inline]
+    fn to_sphere(coords: Vec3) -> Vec4 {
+        coords.extend(1.).normalize()
+    }
+
+    #[inline]
+    fn from_sphere(point: Vec4) -> Vec3 {
+        Vec3::from(point) / point.w
+    }
+
+    fn jacobian(coords: Vec3) -> [Vec4; 3] {
+        let p = coords.extend(1.);
+        let r2 = p.length_squared();
+        let r = r2.sqrt();
+        let column = |e: Vec4, c: f32| (e - p * (c / r2)) / r;
+        [
+            column(Vec4::X, coords.x),
+            column(Vec4::Y, coords.y),
+            column(Vec4::Z, coords.z),
+        ]
+    }
+}
+
+/// The stereographic chart, projecting from `-Vec4::W` onto the tangent space at `Vec4::W`.
+///
+/// It is conformal, so it preserves angles, and it maps spheres to spheres.
+/// It covers everything except `-Vec4::W`; near the origin, distances are doubled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stereographic;
+
+impl Chart for Stereographic {
+    #[inline]
+    fn to_sphere(coords: Vec3) -> Vec4 {
+        let s = 1. + coords.length_squared();
+        (coords * 2.).extend(2. - s) / s
+    }
+
+    #[inline]
+    fn from_sphere(point: Vec4) -> Vec3 {
+        Vec3::from(point) / (1. + point.w)
+    }
+
+    fn jacobian(coords: Vec3) -> [Vec4; 3] {
+        let s = 1. + coords.length_squared();
+        let column = |e: Vec3, c: f32| {
+            (e * (2. / s) - coords * (4. * c / (s * s))).extend(-4. * c / (s * s))
+        };
+        [
+            column(Vec3::X, coords.x),
+            column(Vec3::Y, coords.y),
+            column(Vec3::Z, coords.z),
+        ]
+    }
+}
+
+/// Hyperspherical coordinates `(ψ, θ, φ)`, the 3-sphere's version of spherical coordinates.
+///
+/// `ψ` is the distance from `Vec4::W`, between 0 and π.
+/// `θ` is the angle from the `z` axis, between 0 and π, and `φ` is the angle around it, between -π and π:
+/// `(sin ψ sin θ cos φ, sin ψ sin θ sin φ, sin ψ cos θ, cos ψ)`.
+///
+/// Singular where `sin ψ sin θ = 0`, where `θ` or `φ` is undefined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Hyperspherical;
+
+impl Chart for Hyperspherical {
+    #[inline]
+    fn to_sphere(coords: Vec3) -> Vec4 {
+        let (sin_psi, cos_psi) = coords.x.sin_cos();
+        let (sin_theta, cos_theta) = coords.y.sin_cos();
+        let (sin_phi, cos_phi) = coords.z.sin_cos();
+        Vec4::new(
+            sin_psi * sin_theta * cos_phi,
+            sin_psi * sin_theta * sin_phi,
+            sin_psi * cos_theta,
+            cos_psi,
+        )
+    }
+
+    #[inline]
+    fn from_sphere(point: Vec4) -> Vec3 {
+        let horizontal = point.x.hypot(point.y);
+        Vec3::new(
+            Vec3::from(point).length().atan2(point.w),
+            horizontal.atan2(point.z),
+            point.y.atan2(point.x),
+        )
+    }
+
+    fn jacobian(coords: Vec3) -> [Vec4; 3] {
+        let (sin_psi, cos_psi) = coords.x.sin_cos();
+        let (sin_theta, cos_theta) = coords.y.sin_cos();
+        let (sin_phi, cos_phi) = coords.z.sin_cos();
+        [
+            Vec4::new(
+                cos_psi * sin_theta * cos_phi,
+                cos_psi * sin_theta * sin_phi,
+                cos_psi * cos_theta,
+                -sin_psi,
+            ),
+            Vec4::new(
+                sin_psi * cos_theta * cos_phi,
+                sin_psi * cos_theta * sin_phi,
+                -sin_psi * sin_theta,
+                0.,
+            ),
+            Vec4::new(
+                -sin_psi * sin_theta * sin_phi,
+                sin_psi * sin_theta * cos_phi,
+                0.,
+                0.,
+            ),
+        ]
+    }
+}
+
+/// Hopf coordinates `(η, ξ₁, ξ₂)`, which split the 3-sphere into a family of linked circles.
+///
+/// Reading the point as two complex numbers `x + iy` and `w + iz`,
+/// `η` between 0 and π/2 sets their sizes, and `ξ₁` and `ξ₂` are their phases:
+/// `(sin η cos ξ₁, sin η sin ξ₁, cos η sin ξ₂, cos η cos ξ₂)`.
+/// The origin `Vec4::W` is `(0, 0, 0)`.
+///
+/// Each torus of constant `η` is flat, and the fibers of the Hopf fibration are
+/// the circles along which both `η` and `ξ₁ - ξ₂` are constant.
+/// Singular where `η` is 0 or π/2, where one of the phases is undefined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Hopf;
+
+impl Chart for Hopf {
+    #[inline]
+    fn to_sphere(coords: Vec3) -> Vec4 {
+        let (sin_eta, cos_eta) = coords.x.sin_cos();
+        let (sin_xi1, cos_xi1) = coords.y.sin_cos();
+        let (sin_xi2, cos_xi2) = coords.z.sin_cos();
+        Vec4::new(
+            sin_eta * cos_xi1,
+            sin_eta * sin_xi1,
+            cos_eta * sin_xi2,
+            cos_eta * cos_xi2,
+        )
+    }
+
+    #[inline]
+    fn from_sphere(point: Vec4) -> Vec3 {
+        Vec3::new(
+            point.x.hypot(point.y).atan2(point.z.hypot(point.w)),
+            point.y.atan2(point.x),
+            point.z.atan2(point.w),
+        )
+    }
+
+    fn jacobian(coords: Vec3) -> [Vec4; 3] {
+        let (sin_eta, cos_eta) = coords.x.sin_cos();
+        let (sin_xi1, cos_xi1) = coords.y.sin_cos();
+        let (sin_xi2, cos_xi2) = coords.z.sin_cos();
+        [
+            Vec4::new(
+                cos_eta * cos_xi1,
+                cos_eta * sin_xi1,
+                -sin_eta * sin_xi2,
+                -sin_eta * cos_xi2,
+            ),
+            Vec4::new(-sin_eta * sin_xi1, sin_eta * cos_xi1, 0., 0.),
+            Vec4::new(0., 0., cos_eta * cos_xi2, -cos_eta * sin_xi2),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check_chart<C: Chart>(coords: Vec3) {
+        let point = C::to_sphere(coords);
+        assert!((point.length() - 1.).abs() < 1e-5);
+        assert!(
+            C::from_sphere(point).abs_diff_eq(coords, 1e-4),
+            "{:?} != {:?}",
+            C::from_sphere(point),
+            coords
+        );
+
+        let columns = C::jacobian(coords);
+        let rows = C::inverse_jacobian(point);
+        let h = 1e-3;
+        for (i, e) in [Vec3::X, Vec3::Y, Vec3::Z].iter().enumerate() {
+            let difference =
+                (C::to_sphere(coords + *e * h) - C::to_sphere(coords - *e * h)) / (2. * h);
+            assert!(columns[i].abs_diff_eq(difference, 1e-3));
+            assert!(columns[i].dot(point).abs() < 1e-5);
+            for (j, row) in rows.iter().enumerate() {
+                let expected = if i == j { 1. } else { 0. };
+                assert!((row.dot(columns[i]) - expected).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn charts_invert() {
+        for &coords in &[Vec3::new(0.3, -0.2, 0.5), Vec3::new(1.5, 0.7, -2.0)] {
+            check_chart::<Gnomonic>(coords);
+            check_chart::<Stereographic>(coords);
+        }
+        for &coords in &[Vec3::new(0.3, 1.2, -0.5), Vec3::new(2.5, 0.7, 2.0)] {
+            check_chart::<Hyperspherical>(coords);
+        }
+        for &coords in &[Vec3::new(0.3, 1.2, -0.5), Vec3::new(1.2, -3.0, 2.0)] {
+            check_chart::<Hopf>(coords);
+        }
+    }
+
+    #[test]
+    fn origin_is_w() {
+        assert_eq!(Gnomonic::to_sphere(Vec3::ZERO), Vec4::W);
+        assert_eq!(Stereographic::to_sphere(Vec3::ZERO), Vec4::W);
+        assert_eq!(Hyperspherical::to_sphere(Vec3::ZERO), Vec4::W);
+        assert_eq!(Hopf::to_sphere(Vec3::ZERO), Vec4::W);
+    }
+}
@@ -0,0 +1,223 @@
+use std::ops::*;
+
+use bevy_math::{const_quat, Mat4, Quat, Vec3, Vec4};
+use bevy_reflect::Reflect;
+
+/// A quaternion with complex coefficients, `real + i * imag`,
+/// representing an isometry of hyperbolic space.
+///
+/// Points of hyperbolic space are `Vec4`s on the hyperboloid `x² + y² + z² - w² = -1, w > 0`.
+/// The point `v` is identified with the complex quaternion `w + i * (x, y, z)`,
+/// and is moved to `q * v * conj(q̅)`, where `q̅` conjugates the complex coefficients
+/// and `conj` is the quaternion conjugate.
+///
+/// Unit complex quaternions form the group SL(2,C), which double covers
+/// the group SO⁺(3,1) of orientation-preserving isometries of hyperbolic space;
+/// `q` and `-q` are the same isometry.
+/// This plays the role for hyperbolic space that [`Biquaternion`](crate::biquaternion::Biquaternion)
+/// plays for spherical space.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct ComplexQuaternion {
+    pub real: Quat,
+    pub imag: Quat,
+}
+
+const ZERO_QUAT: Quat = const_quat!([0., 0., 0., 0.]);
+
+impl ComplexQuaternion {
+    pub const IDENTITY: Self = Self {
+        real: Quat::IDENTITY,
+        imag: ZERO_QUAT,
+    };
+
+    /// Creates the isometry that rotates around the origin `Vec4::W` by the given quaternion.
+    #[inline(always)]
+    pub fn from_rotation(q: Quat) -> Self {
+        Self {
+            real: q,
+            imag: ZERO_QUAT,
+        }
+    }
+
+    /// Creates the isometry that translates the origin `Vec4::W` by the given vector.
+    /// This is a Lorentz boost; unlike in spherical space, it never wraps around.
+    #[inline]
+    pub fn from_translation(mut translation: Vec3) -> Self {
+        translation *= 0.5;
+
+        let len = translation.length();
+        let (sinh_len_by_len, cosh_len) = if len < 0.0001 {
+            (1., 1.)
+        } else {
+            (len.sinh() / len, len.cosh())
+        };
+
+        translation *= sinh_len_by_len;
+
+        Self {
+            real: Quat::from_xyzw(0., 0., 0., cosh_len),
+            imag: Quat::from_xyzw(translation.x, translation.y, translation.z, 0.),
+        }
+    }
+
+    /// Returns the quaternion conjugate of `self`. For a unit complex quaternion the
+    /// conjugate is also the inverse.
+    #[inline(always)]
+    pub fn conjugate(self) -> Self {
+        Self {
+            real: self.real.conjugate(),
+            imag: self.imag.conjugate(),
+        }
+    }
+
+    /// Returns the inverse of a normalized complex quaternion.
+    /// Because `self` is assumed to already be unit length,
+    /// this method does not normalize before returning the conjugate.
+    #[inline(always)]
+    pub fn inverse(self) -> Self {
+        self.conjugate()
+    }
+
+    /// Returns the complex conjugate of each coefficient of `self`.
+    #[inline(always)]
+    fn complex_conjugate(self) -> Self {
+        Self {
+            real: self.real,
+            imag: -self.imag,
+        }
+    }
+
+    /// Rescales `self` to unit length, so that it is an isometry again.
+    ///
+    /// A unit complex quaternion has `real · imag = 0` and `|real|² - |imag|² = 1`.
+    #[inline]
+    pub fn normalize(self) -> Self {
+        let imag = self.imag - self.real * (self.real.dot(self.imag) / self.real.length_squared());
+        let norm = (self.real.length_squared() - imag.length_squared()).sqrt();
+        Self {
+            real: self.real / norm,
+            imag: imag / norm,
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_finite(self) -> bool {
+        self.real.is_finite() && self.imag.is_finite()
+    }
+    #[inline(always)]
+    pub fn is_nan(self) -> bool {
+        self.real.is_nan() || self.imag.is_nan()
+    }
+    /// Returns whether `self` has unit length.
+    #[inline(always)]
+    pub fn is_normalized(self) -> bool {
+        (self.real.length_squared() - self.imag.length_squared() - 1.).abs() <= 1e-4
+            && self.real.dot(self.imag).abs() <= 1e-4
+    }
+
+    /// Returns true if the absolute difference of all elements between `self` and `other`
+    /// is less than or equal to `max_abs_diff`.
+    #[inline(always)]
+    pub fn abs_diff_eq(self, other: Self, max_abs_diff: f32) -> bool {
+        self.real.abs_diff_eq(other.real, max_abs_diff)
+            && self.imag.abs_diff_eq(other.imag, max_abs_diff)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl From<ComplexQuaternion> for Mat4 {
+    fn from(cq: ComplexQuaternion) -> Mat4 {
+        Mat4::from_cols(cq * Vec4::X, cq * Vec4::Y, cq * Vec4::Z, cq * Vec4::W)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl Default for ComplexQuaternion {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Neg for ComplexQuaternion {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            real: -self.real,
+            imag: -self.imag,
+        }
+    }
+}
+
+impl Mul for ComplexQuaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            real: self.real * rhs.real - self.imag * rhs.imag,
+            imag: self.real * rhs.imag + self.imag * rhs.real,
+        }
+    }
+}
+
+impl Mul<Vec4> for ComplexQuaternion {
+    type Output = Vec4;
+
+    fn mul(self, rhs: Vec4) -> Vec4 {
+        let v = Self {
+            real: Quat::from_xyzw(0., 0., 0., rhs.w),
+            imag: Quat::from_xyzw(rhs.x, rhs.y, rhs.z, 0.),
+        };
+        let v = self * v * self.complex_conjugate().conjugate();
+        Vec4::new(v.imag.x, v.imag.y, v.imag.z, v.real.w)
+    }
+}
+
+impl MulAssign for ComplexQuaternion {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn minkowski_dot(a: Vec4, b: Vec4) -> f32 {
+        a.x * b.x + a.y * b.y + a.z * b.z - a.w * b.w
+    }
+
+    #[test]
+    fn translation_moves_along_hyperboloid() {
+        let translation = Vec3::new(0.3, -1.0, 0.8);
+        let distance = translation.length();
+        let point = ComplexQuaternion::from_translation(translation) * Vec4::W;
+        let expected = (translation / distance * distance.sinh()).extend(distance.cosh());
+        assert!(point.abs_diff_eq(expected, 1e-5));
+        assert!((minkowski_dot(point, point) + 1.).abs() < 1e-4);
+    }
+
+    #[test]
+    fn composition_matches_matrices() {
+        let a = ComplexQuaternion::from_translation(Vec3::new(0.5, 0.2, -0.4))
+            * ComplexQuaternion::from_rotation(Quat::from_rotation_y(1.0));
+        let b = ComplexQuaternion::from_rotation(Quat::from_rotation_x(-0.3))
+            * ComplexQuaternion::from_translation(Vec3::new(-1.0, 0.0, 0.7));
+        let product = Mat4::from(a * b);
+        let expected = Mat4::from(a) * Mat4::from(b);
+        assert!(product.abs_diff_eq(expected, 1e-4));
+        assert!((a * b).is_normalized());
+    }
+
+    #[test]
+    fn isometries_preserve_minkowski_product() {
+        let isometry = ComplexQuaternion::from_translation(Vec3::new(0.2, 0.9, -0.1))
+            * ComplexQuaternion::from_rotation(Quat::from_rotation_z(0.4));
+        let a = ComplexQuaternion::from_translation(Vec3::X) * Vec4::W;
+        let b = ComplexQuaternion::from_translation(Vec3::new(0.0, -0.5, 0.5)) * Vec4::W;
+        assert!((minkowski_dot(isometry * a, isometry * b) - minkowski_dot(a, b)).abs() < 1e-4);
+        assert!((isometry.inverse() * (isometry * a)).abs_diff_eq(a, 1e-4));
+    }
+}
@@ -0,0 +1,113 @@
+use super::{DTransform, GlobalTransform};
+use bevy_math::{DMat4, DVec4};
+use std::ops::Mul;
+
+use crate::dbiquaternion::DBiquaternion;
+
+/// A double-precision [`GlobalTransform`].
+///
+/// [`DGlobalTransform`] is updated from [`DTransform`] in the system
+/// [`transform_propagate_system`](crate::transform_propagate_system::transform_propagate_system),
+/// and then copied into the entity's [`GlobalTransform`] by
+/// [`dglobal_transform_sync_system`](crate::transform_propagate_system::dglobal_transform_sync_system),
+/// so the renderer can use it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DGlobalTransform {
+    pub biquat: DBiquaternion,
+    /// The product of the [`LocalScale`](super::LocalScale)s of the entity and its ancestors,
+    /// as in [`GlobalTransform::scale`].
+    pub scale: f64,
+}
+
+impl DGlobalTransform {
+    /// Creates a new identity [`DGlobalTransform`], with no translation or rotation, and a scale of 1.
+    #[inline]
+    pub const fn identity() -> Self {
+        DGlobalTransform {
+            biquat: DBiquaternion::IDENTITY,
+            scale: 1.,
+        }
+    }
+
+    pub fn position(&self) -> DVec4 {
+        self.biquat * DVec4::W
+    }
+
+    /// Returns the 4d rotation matrix of this transform.
+    #[inline]
+    pub fn compute_matrix(&self) -> DMat4 {
+        DMat4::from(self.biquat)
+    }
+
+    /// Returns the composite [`DGlobalTransform`] resulting from applying `transform`, followed by `self`.
+    #[inline]
+    pub fn mul_transform(&self, transform: DTransform) -> DGlobalTransform {
+        Self {
+            biquat: self.biquat * transform.biquat,
+            scale: self.scale,
+        }
+    }
+
+    /// Returns a [`DVec4`] of this [`DGlobalTransform`] applied to `value`.
+    #[inline]
+    pub fn mul_vec4(&self, value: DVec4) -> DVec4 {
+        self.biquat * value
+    }
+
+    /// Rounds `self` to single precision.
+    #[inline]
+    pub fn as_f32(&self) -> GlobalTransform {
+        GlobalTransform {
+            biquat: self.biquat.as_f32(),
+            scale: self.scale as f32,
+        }
+    }
+}
+
+impl Default for DGlobalTransform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl From<DTransform> for DGlobalTransform {
+    fn from(transform: DTransform) -> Self {
+        Self {
+            biquat: transform.biquat,
+            scale: 1.,
+        }
+    }
+}
+
+impl From<GlobalTransform> for DGlobalTransform {
+    fn from(transform: GlobalTransform) -> Self {
+        Self {
+            biquat: transform.biquat.as_f64(),
+            scale: transform.scale as f64,
+        }
+    }
+}
+
+impl From<DGlobalTransform> for GlobalTransform {
+    fn from(transform: DGlobalTransform) -> Self {
+        transform.as_f32()
+    }
+}
+
+impl Mul<DTransform> for DGlobalTransform {
+    type Output = DGlobalTransform;
+
+    #[inline]
+    fn mul(self, transform: DTransform) -> Self::Output {
+        self.mul_transform(transform)
+    }
+}
+
+impl Mul<DVec4> for DGlobalTransform {
+    type Output = DVec4;
+
+    #[inline]
+    fn mul(self, value: DVec4) -> Self::Output {
+        self.mul_vec4(value)
+    }
+}
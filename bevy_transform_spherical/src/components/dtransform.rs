@@ -0,0 +1,144 @@
+use super::{DGlobalTransform, Transform};
+use bevy_math::{DQuat, DVec3, DVec4};
+use std::ops::Mul;
+
+use crate::dbiquaternion::DBiquaternion;
+
+/// A double-precision [`Transform`].
+///
+/// Use this for authoritative simulation state that is updated by composing many small motions.
+/// [`DGlobalTransform`] is updated from [`DTransform`] in the same way that
+/// [`GlobalTransform`](super::GlobalTransform) is updated from [`Transform`],
+/// and then rounded into the entity's [`GlobalTransform`](super::GlobalTransform) for rendering.
+///
+/// An entity using [`DTransform`] should have [`DGlobalTransform`] and
+/// [`GlobalTransform`](super::GlobalTransform), but not [`Transform`].
+/// Its parent and children should also use [`DTransform`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DTransform {
+    pub biquat: DBiquaternion,
+}
+
+impl DTransform {
+    /// Creates a new identity [`DTransform`], with no translation or rotation.
+    #[inline]
+    pub const fn identity() -> Self {
+        DTransform {
+            biquat: DBiquaternion::IDENTITY,
+        }
+    }
+
+    /// Creates a new [`DTransform`], that translates by the given vector.
+    /// See [`Transform::from_translation`].
+    #[inline]
+    pub fn from_translation(mut translation: DVec3) -> Self {
+        translation *= 0.5;
+
+        let len = translation.length();
+        let (sin_len_by_len, cos_len) = if len < 1e-8 {
+            (1., 1.)
+        } else {
+            let (s, c) = len.sin_cos();
+            (s / len, c)
+        };
+
+        translation *= sin_len_by_len;
+
+        let quat = DQuat::from_xyzw(translation.x, translation.y, translation.z, cos_len);
+        DTransform {
+            biquat: DBiquaternion {
+                left: quat,
+                right: quat,
+            },
+        }
+    }
+
+    /// Creates a new [`DTransform`], that translates by the given vector.
+    /// Is only accurate when the length of `translation` is much smaller than one,
+    /// which is the size of the spherical universe.
+    #[inline]
+    pub fn from_small_translation(mut translation: DVec3) -> Self {
+        translation *= 0.5;
+        let quat = DQuat::from_xyzw(translation.x, translation.y, translation.z, 1.).normalize();
+        DTransform {
+            biquat: DBiquaternion {
+                left: quat,
+                right: quat,
+            },
+        }
+    }
+
+    /// Creates a new [`DTransform`], that rotates by the given quaternion.
+    #[inline]
+    pub fn from_rotation(rotation: DQuat) -> Self {
+        Self {
+            biquat: DBiquaternion::from_rotation(rotation),
+        }
+    }
+
+    /// Returns the composite [`DTransform`] resulting from applying `transform`, followed by `self`.
+    #[inline]
+    pub fn mul_transform(&self, transform: DTransform) -> Self {
+        DTransform {
+            biquat: self.biquat * transform.biquat,
+        }
+    }
+
+    /// Returns a [`DVec4`] of this [`DTransform`] applied to `value`.
+    #[inline]
+    pub fn mul_vec4(&self, value: DVec4) -> DVec4 {
+        self.biquat * value
+    }
+
+    /// Rounds `self` to single precision.
+    #[inline]
+    pub fn as_f32(&self) -> Transform {
+        Transform {
+            biquat: self.biquat.as_f32(),
+        }
+    }
+}
+
+impl Default for DTransform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl From<DGlobalTransform> for DTransform {
+    fn from(transform: DGlobalTransform) -> Self {
+        Self {
+            biquat: transform.biquat,
+        }
+    }
+}
+
+impl From<Transform> for DTransform {
+    fn from(transform: Transform) -> Self {
+        Self {
+            biquat: transform.biquat.as_f64(),
+        }
+    }
+}
+
+impl From<DTransform> for Transform {
+    fn from(transform: DTransform) -> Self {
+        transform.as_f32()
+    }
+}
+
+impl Mul<DTransform> for DTransform {
+    type Output = DTransform;
+
+    fn mul(self, transform: DTransform) -> Self::Output {
+        self.mul_transform(transform)
+    }
+}
+
+impl Mul<DVec4> for DTransform {
+    type Output = DVec4;
+
+    fn mul(self, value: DVec4) -> Self::Output {
+        self.mul_vec4(value)
+    }
+}
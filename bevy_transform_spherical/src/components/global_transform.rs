@@ -2,9 +2,11 @@ use super::Transform;
 use bevy_ecs::reflect::ReflectComponent;
 use bevy_math::{Mat4, Quat, Vec3, Vec4};
 use bevy_reflect::Reflect;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
 use std::ops::Mul;
 
-use crate::biquaternion::Biquaternion;
+use crate::{biquaternion::Biquaternion, geometry, universe::UniverseRadius};
 
 /// Describe the position of an entity relative to the reference frame.
 ///
@@ -36,9 +38,13 @@ use crate::biquaternion::Biquaternion;
 /// update the[`Transform`] of an entity in this stage or after, you will notice a 1 frame lag
 /// before the [`GlobalTransform`] is updated.
 #[derive(Debug, PartialEq, Clone, Copy, Reflect)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[reflect(Component, PartialEq)]
 pub struct GlobalTransform {
     pub biquat: Biquaternion,
+    /// The product of the [`LocalScale`](super::LocalScale)s of the entity and its ancestors.
+    /// It only affects how the entity's mesh is drawn; see [`GlobalTransform::transform_vertex`].
+    pub scale: f32,
 }
 
 impl GlobalTransform {
@@ -48,24 +54,74 @@ impl GlobalTransform {
     pub const fn identity() -> Self {
         GlobalTransform {
             biquat: Biquaternion::IDENTITY,
+            scale: 1.,
         }
     }
 
+    /// The position of the entity, as a point on the unit 3-sphere.
     pub fn position(&self) -> Vec4 {
         self.biquat * Vec4::W
     }
 
-    //     #[doc(hidden)]
-    //     #[inline]
-    //     pub fn from_matrix(matrix: Mat4) -> Self {
-    //         let (scale, rotation, translation) = matrix.to_scale_rotation_translation();
+    /// The position of the entity in elliptic space, where `p` and `-p` are the same point.
+    /// Of the two representatives, this returns the one in the hemisphere `w >= 0`,
+    /// breaking ties on the equator by the first nonzero coordinate of `z`, `y`, `x`.
+    pub fn elliptic_position(&self) -> Vec4 {
+        let position = self.position();
+        let key = [position.w, position.z, position.y, position.x];
+        match key.iter().find(|&&c| c != 0.) {
+            Some(&c) if c < 0. => -position,
+            _ => position,
+        }
+    }
+
+    /// Follows the geodesic leaving `point` with initial velocity `tangent` for unit time.
+    /// See [`geometry::exp`].
+    ///
+    /// To aim along one of this entity's local axes, start from
+    /// `exp_at(transform.position(), transform.local_tangent(direction))`.
+    #[inline]
+    pub fn exp_at(point: Vec4, tangent: Vec4) -> Vec4 {
+        geometry::exp(point, tangent)
+    }
+
+    /// The velocity at `point` of the shortest geodesic reaching `target` in unit time.
+    /// See [`geometry::log`].
+    #[inline]
+    pub fn log_at(point: Vec4, target: Vec4) -> Vec4 {
+        geometry::log(point, target)
+    }
 
-    //         GlobalTransform {
-    //             translation,
-    //             rotation,
-    //             scale,
-    //         }
-    //     }
+    /// The tangent vector at [`GlobalTransform::position`] pointing along `direction`,
+    /// given in the entity's local coordinates.
+    #[inline]
+    pub fn local_tangent(&self, direction: Vec3) -> Vec4 {
+        self.biquat * direction.extend(0.)
+    }
+
+    /// The inverse of [`GlobalTransform::local_tangent`]:
+    /// expresses a tangent vector at [`GlobalTransform::position`] in the entity's local coordinates.
+    #[inline]
+    pub fn to_local_tangent(&self, tangent: Vec4) -> Vec3 {
+        (self.biquat.inverse() * tangent).into()
+    }
+
+    /// The position of the entity on the 3-sphere of the given radius, in world units.
+    pub fn position_in(&self, radius: UniverseRadius) -> Vec4 {
+        self.position() * radius.0
+    }
+
+    /// Creates a [`GlobalTransform`] from a 4x4 rotation matrix, such as one returned by
+    /// [`GlobalTransform::compute_matrix`].
+    /// If `matrix` is not quite orthogonal, it is projected onto the nearest rotation;
+    /// see [`Biquaternion::from_mat4`].
+    #[inline]
+    pub fn from_matrix(matrix: Mat4) -> Self {
+        GlobalTransform {
+            biquat: Biquaternion::from_mat4(matrix),
+            scale: 1.,
+        }
+    }
 
     #[doc(hidden)]
     #[inline]
@@ -85,31 +141,62 @@ impl GlobalTransform {
         Transform::from_rotation(rotation).into()
     }
 
-    //     #[doc(hidden)]
-    //     #[inline]
-    //     pub fn looking_at(mut self, target: Vec3, up: Vec3) -> Self {
-    //         self.look_at(target, up);
-    //         self
-    //     }
+    #[doc(hidden)]
+    #[inline]
+    pub fn looking_at(mut self, target: Vec4, up: Vec4) -> Self {
+        self.look_at(target, up);
+        self
+    }
 
-    /// Returns the 3d affine transformation matrix from this transforms translation,
-    /// rotation, and scale.
+    /// Returns the 4x4 rotation matrix of this [`GlobalTransform`],
+    /// which acts on points of the unit 3-sphere written as column vectors.
     #[inline]
     pub fn compute_matrix(&self) -> Mat4 {
         Mat4::from(self.biquat)
     }
 
-    //     #[doc(hidden)]
-    //     #[inline]
-    //     pub fn rotate(&mut self, rotation: Quat) {
-    //         self.rotation *= rotation;
-    //     }
+    #[doc(hidden)]
+    #[inline]
+    pub fn rotate(&mut self, rotation: Quat) {
+        self.biquat *= Biquaternion::from_rotation(rotation);
+    }
+
+    /// Returns [`GlobalTransform::compute_matrix`] with the [`GlobalTransform::scale`] applied first,
+    /// in the gnomonic chart.
+    ///
+    /// Its columns are not unit vectors unless the scale is 1,
+    /// so points must be normalized after applying it.
+    #[inline]
+    pub fn compute_scaled_matrix(&self) -> Mat4 {
+        self.compute_matrix() * Mat4::from_scale(Vec3::splat(self.scale))
+    }
+
+    /// Where the mesh vertex `vertex` is drawn, as a point on the unit 3-sphere.
+    /// `vertex` is in units of the [`UniverseRadius`], and is read in the
+    /// [`Gnomonic`](crate::charts::Gnomonic) chart after multiplying by [`GlobalTransform::scale`].
+    ///
+    /// This is what `pbr.vert` computes.
+    #[inline]
+    pub fn transform_vertex(&self, vertex: Vec3) -> Vec4 {
+        self.biquat * (vertex * self.scale).extend(1.).normalize()
+    }
+
+    /// The unit normal drawn at a vertex whose mesh normal is `normal`, as a tangent vector of the
+    /// unit 3-sphere. This is the inverse transpose of the scaled matrix, so the result stays
+    /// orthogonal to the surface drawn by [`GlobalTransform::transform_vertex`].
+    ///
+    /// This is what `pbr.vert` computes.
+    #[inline]
+    pub fn transform_normal(&self, normal: Vec4) -> Vec4 {
+        (self.biquat * normal.truncate().extend(normal.w * self.scale)).normalize()
+    }
 
     /// Returns the composite [`GlobalTransform`] resulting from applying `transform`, followed by `self`.
     #[inline]
     pub fn mul_transform(&self, transform: Transform) -> GlobalTransform {
         Self {
             biquat: self.biquat * transform.biquat,
+            scale: self.scale,
         }
     }
 
@@ -119,14 +206,13 @@ impl GlobalTransform {
         self.biquat * value
     }
 
-    //     #[doc(hidden)]
-    //     #[inline]
-    //     pub fn look_at(&mut self, target: Vec3, up: Vec3) {
-    //         let forward = Vec3::normalize(self.translation - target);
-    //         let right = up.cross(forward).normalize();
-    //         let up = forward.cross(right);
-    //         self.rotation = Quat::from_rotation_mat3(&Mat3::from_cols(right, up, forward));
-    //     }
+    #[doc(hidden)]
+    #[inline]
+    pub fn look_at(&mut self, target: Vec4, up: Vec4) {
+        let mut transform = Transform::from(*self);
+        transform.look_at(target, up);
+        self.biquat = transform.biquat;
+    }
 }
 
 impl Default for GlobalTransform {
@@ -139,6 +225,7 @@ impl From<Transform> for GlobalTransform {
     fn from(transform: Transform) -> Self {
         Self {
             biquat: transform.biquat,
+            scale: 1.,
         }
     }
 }
@@ -190,13 +277,16 @@ mod render {
             Some(RenderResourceType::Buffer)
         }
 
+        // The unscaled matrix and the scale, padded to std140:
+        // `uniform Transform { mat4 Model; float Scale; }`.
         fn write_buffer_bytes(&self, buffer: &mut [u8]) {
-            let mat4 = self.compute_matrix();
-            mat4.write_bytes(buffer);
+            let (model, scale) = buffer.split_at_mut(std::mem::size_of::<[f32; 16]>());
+            self.compute_matrix().write_bytes(model);
+            [self.scale, 0., 0., 0.].write_bytes(scale);
         }
 
         fn buffer_byte_len(&self) -> Option<usize> {
-            Some(std::mem::size_of::<[f32; 16]>())
+            Some(std::mem::size_of::<[f32; 20]>())
         }
 
         fn texture(&self) -> Option<&Handle<Texture>> {
@@ -230,3 +320,125 @@ mod render {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        charts::{Chart, Gnomonic},
+        geometry,
+    };
+
+    #[test]
+    fn scaled_vertices() {
+        let transform = GlobalTransform {
+            scale: 3.,
+            ..GlobalTransform::from_translation(Vec3::new(0.5, -1.0, 0.2))
+        };
+        let position = transform.position();
+
+        // The scaled matrix draws vertices in the same place.
+        for &vertex in &[
+            Vec3::ZERO,
+            Vec3::new(0.01, 0.0, -0.02),
+            Vec3::new(0.4, 0.3, 0.0),
+        ] {
+            let drawn = (transform.compute_scaled_matrix() * vertex.extend(1.)).normalize();
+            assert!(drawn.abs_diff_eq(transform.transform_vertex(vertex), 1e-5));
+        }
+
+        // Near the entity, scaling is nearly uniform.
+        let small = Vec3::new(0.001, 0.002, 0.0);
+        let distance = geometry::distance(position, transform.transform_vertex(small));
+        assert!((distance - 3. * small.length()).abs() < 1e-5);
+
+        // Far from it, vertices at distance `r` move to `atan(scale * tan(r))`,
+        // and never reach the equator of the gnomonic chart.
+        let far = Vec3::new(0.0, 0.6, 0.8);
+        let r = geometry::distance(Vec4::W, Gnomonic::to_sphere(far));
+        let distance = geometry::distance(position, transform.transform_vertex(far));
+        assert!((distance - (3. * r.tan()).atan()).abs() < 1e-5);
+        assert!(distance < 3. * r);
+        let huge = GlobalTransform {
+            scale: 1e6,
+            ..transform
+        };
+        assert!(
+            geometry::distance(position, huge.transform_vertex(far)) < std::f32::consts::FRAC_PI_2
+        );
+    }
+
+    #[test]
+    fn scaled_normals() {
+        let transform = GlobalTransform {
+            scale: 3.,
+            ..GlobalTransform::from(
+                Transform::from_translation(Vec3::new(0.5, -1.0, 0.2))
+                    * Transform::from_rotation(Quat::from_rotation_x(0.7)),
+            )
+        };
+        let vertex = Vec3::new(0.3, -0.2, 0.1);
+        let (d1, d2) = (Vec3::new(1.0, 0.5, 0.0), Vec3::new(0.0, -0.3, 1.0));
+        let eps = 1e-3;
+        // The point and tangents of the plane through `vertex` spanned by `d1` and `d2`.
+        let frame = |map: &dyn Fn(Vec3) -> Vec4| {
+            [
+                map(vertex),
+                map(vertex + d1 * eps) - map(vertex - d1 * eps),
+                map(vertex + d2 * eps) - map(vertex - d2 * eps),
+            ]
+        };
+
+        // The mesh normal is orthogonal to all of them.
+        let mut basis: Vec<Vec4> = Vec::new();
+        for &v in frame(&|v| v.extend(1.).normalize()).iter() {
+            let v = basis.iter().fold(v, |v, b| v - *b * v.dot(*b));
+            basis.push(v.normalize());
+        }
+        let normal = basis
+            .iter()
+            .fold(Vec4::new(0.1, 0.9, 0.2, 0.4), |n, b| n - *b * n.dot(*b))
+            .normalize();
+
+        // So is the drawn normal to the drawn point and tangents.
+        let drawn = transform.transform_normal(normal);
+        assert!((drawn.length() - 1.).abs() < 1e-5);
+        for &v in frame(&|v| transform.transform_vertex(v)).iter() {
+            assert!(drawn.dot(v.normalize()).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn look_at_keeps_scale() {
+        let start = GlobalTransform {
+            scale: 2.,
+            ..GlobalTransform::from_translation(Vec3::new(0.3, 0.1, -0.2))
+        };
+        let target = GlobalTransform::from_translation(Vec3::new(-0.4, 0.5, 0.0)).position();
+        let global = start.looking_at(target, Vec4::Y);
+        let transform = Transform::from(start).looking_at(target, Vec4::Y);
+        assert_eq!(global.scale, 2.);
+        assert!(global.biquat.abs_diff_eq(transform.biquat, 1e-6));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn serde_round_trip() {
+        let global = GlobalTransform {
+            scale: 2.5,
+            ..GlobalTransform::from(
+                Transform::from_translation(Vec3::new(0.2, -0.6, 1.3))
+                    * Transform::from_rotation(Quat::from_rotation_z(0.4)),
+            )
+        };
+        let round_trip: GlobalTransform = ron::from_str(&ron::to_string(&global).unwrap()).unwrap();
+        assert!(round_trip.biquat.abs_diff_eq(global.biquat, 1e-5));
+        assert_eq!(round_trip.scale, global.scale);
+
+        let transform: Transform =
+            ron::from_str("(biquat: (translation: (0.0, 0.0, -0.5)))").unwrap();
+        assert!(transform
+            .biquat
+            .abs_diff_eq(Transform::from_translation(-Vec3::Z * 0.5).biquat, 1e-6));
+    }
+}
@@ -0,0 +1,97 @@
+use super::HyperbolicTransform;
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_math::{Mat4, Quat, Vec3, Vec4};
+use bevy_reflect::Reflect;
+use std::ops::Mul;
+
+use crate::complex_quaternion::ComplexQuaternion;
+
+/// Describe the position of an entity in hyperbolic space, relative to the reference frame.
+///
+/// This is the hyperbolic counterpart of [`GlobalTransform`](super::GlobalTransform).
+/// It is updated from [`HyperbolicTransform`] in the system
+/// [`transform_propagate_system`](crate::transform_propagate_system::transform_propagate_system).
+#[derive(Debug, PartialEq, Clone, Copy, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct HyperbolicGlobalTransform {
+    pub cquat: ComplexQuaternion,
+}
+
+impl HyperbolicGlobalTransform {
+    /// Creates a new identity [`HyperbolicGlobalTransform`], with no translation or rotation.
+    #[inline]
+    pub const fn identity() -> Self {
+        HyperbolicGlobalTransform {
+            cquat: ComplexQuaternion::IDENTITY,
+        }
+    }
+
+    /// The position of the entity, as a point on the hyperboloid.
+    pub fn position(&self) -> Vec4 {
+        self.cquat * Vec4::W
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn from_translation(translation: Vec3) -> Self {
+        HyperbolicTransform::from_translation(translation).into()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn from_rotation(rotation: Quat) -> Self {
+        HyperbolicTransform::from_rotation(rotation).into()
+    }
+
+    /// Returns the 4d Lorentz transformation matrix of this transform.
+    #[inline]
+    pub fn compute_matrix(&self) -> Mat4 {
+        Mat4::from(self.cquat)
+    }
+
+    /// Returns the composite [`HyperbolicGlobalTransform`] resulting from applying `transform`, followed by `self`.
+    #[inline]
+    pub fn mul_transform(&self, transform: HyperbolicTransform) -> HyperbolicGlobalTransform {
+        Self {
+            cquat: self.cquat * transform.cquat,
+        }
+    }
+
+    /// Returns a [`Vec4`] of this [`HyperbolicGlobalTransform`] applied to `value`.
+    #[inline]
+    pub fn mul_vec4(&self, value: Vec4) -> Vec4 {
+        self.cquat * value
+    }
+}
+
+impl Default for HyperbolicGlobalTransform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl From<HyperbolicTransform> for HyperbolicGlobalTransform {
+    fn from(transform: HyperbolicTransform) -> Self {
+        Self {
+            cquat: transform.cquat,
+        }
+    }
+}
+
+impl Mul<HyperbolicTransform> for HyperbolicGlobalTransform {
+    type Output = HyperbolicGlobalTransform;
+
+    #[inline]
+    fn mul(self, transform: HyperbolicTransform) -> Self::Output {
+        self.mul_transform(transform)
+    }
+}
+
+impl Mul<Vec4> for HyperbolicGlobalTransform {
+    type Output = Vec4;
+
+    #[inline]
+    fn mul(self, value: Vec4) -> Self::Output {
+        self.mul_vec4(value)
+    }
+}
@@ -0,0 +1,182 @@
+use super::HyperbolicGlobalTransform;
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_math::{Mat3, Mat4, Quat, Vec3, Vec4};
+use bevy_reflect::Reflect;
+use std::ops::Mul;
+
+use crate::complex_quaternion::ComplexQuaternion;
+
+/// Describe the position of an entity in hyperbolic space. If the entity has a parent,
+/// the position is relative to its parent position.
+///
+/// This is the hyperbolic counterpart of [`Transform`](super::Transform).
+/// Points are `Vec4`s on the hyperboloid `x² + y² + z² - w² = -1, w > 0`,
+/// and the origin is `Vec4::W`.
+///
+/// [`HyperbolicGlobalTransform`] is updated from [`HyperbolicTransform`] in the system
+/// [`transform_propagate_system`](crate::transform_propagate_system::transform_propagate_system),
+/// exactly as [`GlobalTransform`](super::GlobalTransform) is updated from [`Transform`](super::Transform).
+#[derive(Debug, PartialEq, Clone, Copy, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct HyperbolicTransform {
+    pub cquat: ComplexQuaternion,
+}
+
+impl HyperbolicTransform {
+    /// Creates a new identity [`HyperbolicTransform`], with no translation or rotation.
+    #[inline]
+    pub const fn identity() -> Self {
+        HyperbolicTransform {
+            cquat: ComplexQuaternion::IDENTITY,
+        }
+    }
+
+    /// Creates a new [`HyperbolicTransform`], that translates by the given vector.
+    #[inline]
+    pub fn from_translation(translation: Vec3) -> Self {
+        Self {
+            cquat: ComplexQuaternion::from_translation(translation),
+        }
+    }
+
+    /// Creates a new [`HyperbolicTransform`], that rotates by the given quaternion.
+    #[inline]
+    pub fn from_rotation(rotation: Quat) -> Self {
+        Self {
+            cquat: ComplexQuaternion::from_rotation(rotation),
+        }
+    }
+
+    /// Updates and returns this [`HyperbolicTransform`] by rotating it so that its unit vector in the
+    /// local z direction is toward `target` and its unit vector in the local y direction
+    /// is toward `up`.
+    #[inline]
+    pub fn looking_at(mut self, target: Vec4, up: Vec4) -> Self {
+        self.look_at(target, up);
+        self
+    }
+
+    /// Returns the 4d Lorentz transformation matrix of this transform.
+    #[inline]
+    pub fn compute_matrix(&self) -> Mat4 {
+        Mat4::from(self.cquat)
+    }
+
+    /// Returns the composite [`HyperbolicTransform`] resulting from applying `transform`, followed by `self`.
+    #[inline]
+    pub fn mul_transform(&self, transform: HyperbolicTransform) -> Self {
+        HyperbolicTransform {
+            cquat: self.cquat * transform.cquat,
+        }
+    }
+
+    /// Returns a [`Vec4`] of this [`HyperbolicTransform`] applied to `value`.
+    #[inline]
+    pub fn mul_vec4(&self, value: Vec4) -> Vec4 {
+        self.cquat * value
+    }
+
+    /// Rotates this [`HyperbolicTransform`] so that its unit vector in the local -z direction
+    /// is toward `target` and its unit vector in the local y direction is toward `up`.
+    ///
+    /// As with [`Transform::look_at`](super::Transform::look_at), if `target` is the
+    /// current position the forward axis is kept, and if `up` is along the forward axis
+    /// another up direction is chosen.
+    #[inline]
+    pub fn look_at(&mut self, target: Vec4, up: Vec4) {
+        // Convert from world space to body space, where the position is `Vec4::W`
+        // and the tangent space is spanned by `x`, `y` and `z`.
+        let inv = self.cquat.inverse();
+        let toward: Vec3 = (inv * target).into();
+        let forward = if toward.length_squared() < 1e-12 {
+            Vec3::Z
+        } else {
+            -toward.normalize()
+        };
+
+        // Calculate the rotation, in body space.
+        let up: Vec3 = (inv * up).into();
+        let right = [up, Vec3::Y, Vec3::X]
+            .iter()
+            .map(|up| up.cross(forward))
+            .find(|right| right.length_squared() > 1e-12)
+            .unwrap()
+            .normalize();
+        let up = forward.cross(right);
+        let rotation = Quat::from_rotation_mat3(&Mat3::from_cols(right, up, forward));
+
+        self.cquat *= ComplexQuaternion::from_rotation(rotation);
+    }
+}
+
+impl Default for HyperbolicTransform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl From<HyperbolicGlobalTransform> for HyperbolicTransform {
+    fn from(transform: HyperbolicGlobalTransform) -> Self {
+        Self {
+            cquat: transform.cquat,
+        }
+    }
+}
+
+impl Mul<HyperbolicTransform> for HyperbolicTransform {
+    type Output = HyperbolicTransform;
+
+    fn mul(self, transform: HyperbolicTransform) -> Self::Output {
+        self.mul_transform(transform)
+    }
+}
+
+impl Mul<Vec4> for HyperbolicTransform {
+    type Output = Vec4;
+
+    fn mul(self, value: Vec4) -> Self::Output {
+        self.mul_vec4(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn look_at_points_toward_target() {
+        let mut transform = HyperbolicTransform::from_translation(Vec3::new(0.3, -0.2, 0.5))
+            * HyperbolicTransform::from_rotation(Quat::from_rotation_x(1.0));
+        let position = transform * Vec4::W;
+        let target = HyperbolicTransform::from_translation(Vec3::new(-1.0, 0.4, 2.0)) * Vec4::W;
+        transform.look_at(target, Vec4::Y);
+        assert!(transform.cquat.is_normalized());
+        assert!((transform * Vec4::W).abs_diff_eq(position, 1e-4));
+
+        // In body space, the target is straight along -z, and `up` is above the horizon.
+        let inv = transform.cquat.inverse();
+        let toward: Vec3 = (inv * target).into();
+        assert!(toward.normalize().abs_diff_eq(-Vec3::Z, 1e-4));
+        let up: Vec3 = (inv * Vec4::Y).into();
+        assert!(up.y > 0.);
+        assert!(up.x.abs() < 1e-4);
+    }
+
+    #[test]
+    fn look_at_degenerate() {
+        let transform = HyperbolicTransform::from_translation(Vec3::new(0.3, -0.2, 0.5));
+        let position = transform * Vec4::W;
+
+        // At the current position, the forward axis is kept.
+        let looking = transform.looking_at(position, Vec4::Y);
+        assert!(looking.cquat.is_finite());
+        assert!((looking * Vec4::Z).abs_diff_eq(transform * Vec4::Z, 1e-4));
+
+        // Straight up, the result is still a rotation.
+        let target = transform * HyperbolicTransform::from_translation(Vec3::Y) * Vec4::W;
+        let looking = transform.looking_at(target, transform * Vec4::Y);
+        assert!(looking.cquat.is_finite());
+        assert!(looking.cquat.is_normalized());
+        assert!((looking * -Vec4::Z).abs_diff_eq(transform * Vec4::Y, 1e-4));
+    }
+}
@@ -0,0 +1,32 @@
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_reflect::Reflect;
+
+/// A uniform scale for an entity's mesh, relative to its parent's scale.
+///
+/// Isometries of the 3-sphere cannot change sizes, so scale is kept outside of
+/// [`Transform`](super::Transform). Instead, each mesh vertex `v` is multiplied by the scale
+/// in the [`Gnomonic`](crate::charts::Gnomonic) chart of the entity, before the isometry is applied;
+/// see [`GlobalTransform::transform_vertex`](super::GlobalTransform::transform_vertex).
+///
+/// [`transform_propagate_system`](crate::transform_propagate_system::transform_propagate_system)
+/// multiplies the scales down the hierarchy into [`GlobalTransform::scale`](super::GlobalTransform::scale).
+/// An entity without a [`LocalScale`] has the same scale as its parent.
+///
+/// ## Limits
+///
+/// Scaling is not an isometry, and there is no similarity of the 3-sphere to fall back on:
+/// - A child's position is not scaled along with its parent's mesh,
+///   because that would need to move the child along a non-isometric map.
+///   Children of a scaled parent keep the distances given by their [`Transform`](super::Transform)s.
+/// - Scaling in the gnomonic chart is only close to uniform near the entity's position.
+///   A vertex at distance `r` ends up at distance `atan(scale * tan(r))`, not `scale * r`,
+///   so large meshes are distorted, and no vertex gets as far as π/2 however large the scale.
+#[derive(Debug, PartialEq, Clone, Copy, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct LocalScale(pub f32);
+
+impl Default for LocalScale {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
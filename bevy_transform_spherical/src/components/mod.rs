@@ -5,10 +5,22 @@ mod parent {
     pub use ::bevy_transform::components::{Parent, PreviousParent};
 }
 
+mod dglobal_transform;
+mod dtransform;
 mod global_transform;
+mod hyperbolic_global_transform;
+mod hyperbolic_transform;
+mod local_scale;
+mod previous_transform;
 mod transform;
 
 pub use children::Children;
+pub use dglobal_transform::*;
+pub use dtransform::*;
 pub use global_transform::*;
+pub use hyperbolic_global_transform::*;
+pub use hyperbolic_transform::*;
+pub use local_scale::*;
 pub use parent::{Parent, PreviousParent};
+pub use previous_transform::*;
 pub use transform::*;
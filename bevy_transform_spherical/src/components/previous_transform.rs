@@ -0,0 +1,22 @@
+use super::Transform;
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_reflect::Reflect;
+
+/// The [`Transform`] of an entity as of the previous fixed simulation step.
+///
+/// Add this to a root entity whose [`Transform`] is updated in the
+/// [`FIXED_UPDATE`](crate::interpolation::FIXED_UPDATE) stage, and its [`GlobalTransform`](super::GlobalTransform),
+/// along with those of its descendants, is drawn partway between the last two simulation states;
+/// see [`transform_interpolate_system`](crate::interpolation::transform_interpolate_system).
+///
+/// This is written automatically at the start of each fixed step; do not change it yourself.
+/// It has no effect on entities with a [`Parent`](super::Parent), which move with their parent.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct PreviousTransform(pub Transform);
+
+impl From<Transform> for PreviousTransform {
+    fn from(transform: Transform) -> Self {
+        Self(transform)
+    }
+}
@@ -1,10 +1,12 @@
 use super::GlobalTransform;
 use bevy_ecs::reflect::ReflectComponent;
-use bevy_math::{Mat3, Quat, Vec3, Vec4};
+use bevy_math::{Mat3, Mat4, Quat, Vec3, Vec4};
 use bevy_reflect::Reflect;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
 use std::ops::Mul;
 
-use crate::biquaternion::Biquaternion;
+use crate::{biquaternion::Biquaternion, bivector::quat_log, universe::UniverseRadius};
 
 /// Describe the position of an entity. If the entity has a parent, the position is relative
 /// to its parent position.
@@ -37,6 +39,7 @@ use crate::biquaternion::Biquaternion;
 /// update the[`Transform`] of an entity in this stage or after, you will notice a 1 frame lag
 /// before the [`GlobalTransform`] is updated.
 #[derive(Debug, PartialEq, Clone, Copy, Reflect)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[reflect(Component, PartialEq)]
 pub struct Transform {
     pub biquat: Biquaternion,
@@ -93,6 +96,21 @@ impl Transform {
         }
     }
 
+    /// Creates a new [`Transform`], that translates by the given vector, measured in world units
+    /// of a universe with the given radius.
+    #[inline]
+    pub fn from_translation_in(translation: Vec3, radius: UniverseRadius) -> Self {
+        Self::from_translation(translation / radius.0)
+    }
+
+    /// Creates a new [`Transform`], that translates by the given vector, measured in world units
+    /// of a universe with the given radius.
+    /// Is only accurate when the length of `translation` is much smaller than `radius`.
+    #[inline]
+    pub fn from_small_translation_in(translation: Vec3, radius: UniverseRadius) -> Self {
+        Self::from_small_translation(translation / radius.0)
+    }
+
     /// Creates a new [`Transform`], that rotates by the given quaternion.
     #[inline]
     pub fn from_rotation(rotation: Quat) -> Self {
@@ -101,27 +119,61 @@ impl Transform {
         }
     }
 
-    /// Updates and returns this [`Transform`] by rotating it so that its unit vector in the
-    /// local z direction is toward `target` and its unit vector in the local y direction
-    /// is toward `up`.
+    /// Creates a new [`Transform`] from a 4x4 rotation matrix.
+    /// If `matrix` is not quite orthogonal, it is projected onto the nearest rotation;
+    /// see [`Biquaternion::from_mat4`].
+    #[inline]
+    pub fn from_matrix(matrix: Mat4) -> Self {
+        Transform {
+            biquat: Biquaternion::from_mat4(matrix),
+        }
+    }
+
+    /// Returns this [`Transform`] rotated so that its local `-z` axis points toward `target`
+    /// and its local `y` axis points as nearly as possible along `up`.
+    /// See [`Transform::look_at`].
     #[inline]
     pub fn looking_at(mut self, target: Vec4, up: Vec4) -> Self {
         self.look_at(target, up);
         self
     }
 
-    //     /// Returns the 3d affine transformation matrix from this transforms translation,
-    //     /// rotation, and scale.
-    //     #[inline]
-    //     pub fn compute_matrix(&self) -> Mat4 {
-    //         Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
-    //     }
+    /// Returns the 4x4 rotation matrix of this [`Transform`],
+    /// which acts on points of the unit 3-sphere written as column vectors.
+    #[inline]
+    pub fn compute_matrix(&self) -> Mat4 {
+        Mat4::from(self.biquat)
+    }
+
+    /// Rotates this [`Transform`] in place by the given rotation, given in its local axes.
+    /// Its position does not change.
+    #[inline]
+    pub fn rotate(&mut self, rotation: Quat) {
+        self.biquat *= Biquaternion::from_rotation(rotation);
+    }
 
-    //     /// Rotates the transform by the given rotation.
-    //     #[inline]
-    //     pub fn rotate(&mut self, rotation: Quat) {
-    //         self.rotation *= rotation;
-    //     }
+    /// Splits this [`Transform`] into a translation and a rotation,
+    /// so that it equals `Transform::from_translation(translation) * Transform::from_rotation(rotation)`.
+    ///
+    /// The translation has length at most π. A longer translation is the same as a
+    /// shorter one in the opposite direction, so that is what is returned instead.
+    ///
+    /// At length exactly π, the position is the antipode of the origin, which is reached by
+    /// translating π in any direction. In that case, the translation is taken to be
+    /// along the local forward axis, `-Vec3::Z`, and the rotation is whatever makes up the difference.
+    #[inline]
+    pub fn to_translation_rotation(&self) -> (Vec3, Quat) {
+        // With `left = t * q` and `right = q⁻¹ * t`, `left * right = t²`.
+        let square = self.biquat.left * self.biquat.right;
+        let sum = square + Quat::IDENTITY;
+        let t = if sum.length_squared() < 1e-8 {
+            Quat::from_xyzw(0., 0., -1., 0.)
+        } else {
+            sum.normalize()
+        };
+        let rotation = (t.conjugate() * self.biquat.left).normalize();
+        (quat_log(t) * 2., rotation)
+    }
 
     /// Returns the composite [`Transform`] resulting from applying `transform`, followed by `self`.
     #[inline]
@@ -137,17 +189,37 @@ impl Transform {
         self.biquat * value
     }
 
-    /// Rotates this [`Transform`] so that its unit vector in the local z direction is toward
-    /// `target` and its unit vector in the local y direction is toward `up`.
+    /// Rotates this [`Transform`] in place so that its local `-z` axis points along the shortest
+    /// geodesic toward `target`, and its local `y` axis points as nearly as possible along `up`.
+    /// Its position does not change.
+    ///
+    /// `target` is a point on the unit 3-sphere, and `up` is any vector,
+    /// of which only the part tangent at this [`Transform`]'s position is used.
+    ///
+    /// If `target` is this [`Transform`]'s position or its antipode, every direction leads there,
+    /// and the current forward axis is kept.
+    /// If `up` is parallel to the forward axis, the current `y` axis is used instead,
+    /// or the current `x` axis if that is parallel too.
     #[inline]
     pub fn look_at(&mut self, target: Vec4, up: Vec4) {
-        // Convert from world space to body space.
+        // Convert from world space to body space, where the position is `Vec4::W`
+        // and the tangent space is spanned by `x`, `y` and `z`.
         let inv = self.biquat.inverse();
-        let forward: Vec3 = (inv * -target).into();
-        let up: Vec3 = (inv * up).into();
+        let toward: Vec3 = (inv * target).into();
+        let forward = if toward.length_squared() < 1e-12 {
+            Vec3::Z
+        } else {
+            -toward.normalize()
+        };
 
         // Calculate the rotation, in body space.
-        let right = up.cross(forward).normalize();
+        let up: Vec3 = (inv * up).into();
+        let right = [up, Vec3::Y, Vec3::X]
+            .iter()
+            .map(|up| up.cross(forward))
+            .find(|right| right.length_squared() > 1e-12)
+            .unwrap()
+            .normalize();
         let up = forward.cross(right);
         let rotation = Quat::from_rotation_mat3(&Mat3::from_cols(right, up, forward));
 
@@ -189,3 +261,91 @@ impl Mul<Vec4> for Transform {
 // TODO: Should I have a separate `Position` type?
 // Argument in favor: will probably want that in hyperbolic case, because rounding error.
 // But where should I put the module?
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn translation_rotation_round_trip() {
+        let rotation = Quat::from_axis_angle(Vec3::new(1.0, -2.0, 0.5).normalize(), 2.0);
+        for &translation in &[
+            Vec3::ZERO,
+            Vec3::new(0.3, -0.1, 0.2),
+            Vec3::new(-2.0, 1.0, 1.5),
+            Vec3::new(0.0, 3.1, 0.0),
+        ] {
+            let transform =
+                Transform::from_translation(translation) * Transform::from_rotation(rotation);
+            let (t, q) = transform.to_translation_rotation();
+            assert!(
+                t.abs_diff_eq(translation, 1e-4),
+                "{:?} != {:?}",
+                t,
+                translation
+            );
+            assert!(q.abs_diff_eq(rotation, 1e-4) || q.abs_diff_eq(-rotation, 1e-4));
+        }
+    }
+
+    #[test]
+    fn translation_rotation_antipode() {
+        let transform = Transform::from_translation(Vec3::X * std::f32::consts::PI)
+            * Transform::from_rotation(Quat::from_rotation_y(0.4));
+        let (t, q) = transform.to_translation_rotation();
+        assert!(t.abs_diff_eq(-Vec3::Z * std::f32::consts::PI, 1e-4));
+        let rebuilt = Transform::from_translation(t) * Transform::from_rotation(q);
+        assert!(Mat4::from(rebuilt.biquat).abs_diff_eq(Mat4::from(transform.biquat), 1e-4));
+
+        // Translations longer than π come back the short way around.
+        let (t, _) = Transform::from_translation(Vec3::Y * 4.0).to_translation_rotation();
+        assert!(t.abs_diff_eq(Vec3::Y * (4.0 - std::f32::consts::TAU), 1e-4));
+    }
+
+    #[test]
+    fn look_at_points_toward_target() {
+        let mut transform = Transform::from_translation(Vec3::new(0.3, -0.2, 0.5))
+            * Transform::from_rotation(Quat::from_rotation_x(1.0));
+        let position = transform * Vec4::W;
+        let target = Transform::from_translation(Vec3::new(-1.0, 0.4, 2.0)) * Vec4::W;
+        transform.look_at(target, Vec4::Y);
+
+        assert!((transform * Vec4::W).abs_diff_eq(position, 1e-5));
+        let forward = crate::geometry::log(position, target).normalize();
+        assert!((transform * -Vec4::Z).abs_diff_eq(forward, 1e-4));
+        let up = Vec4::Y - position * position.dot(Vec4::Y);
+        assert!((transform * Vec4::Y).dot(up) > 0.);
+        assert!((transform * Vec4::X).dot(up).abs() < 1e-4);
+    }
+
+    #[test]
+    fn look_at_degenerate() {
+        let transform = Transform::from_translation(Vec3::new(0.3, -0.2, 0.5));
+        let position = transform * Vec4::W;
+
+        // At the antipode, the forward axis is kept.
+        let looking = transform.looking_at(-position, Vec4::Y);
+        assert!(looking.biquat.is_finite());
+        assert!((looking * Vec4::Z).abs_diff_eq(transform * Vec4::Z, 1e-4));
+        let looking = transform.looking_at(position, Vec4::Y);
+        assert!((looking * Vec4::Z).abs_diff_eq(transform * Vec4::Z, 1e-4));
+
+        // With `up` along the forward axis, the result is still a rotation.
+        let target = transform * Transform::from_translation(Vec3::Y) * Vec4::W;
+        let looking = transform.looking_at(target, transform * Vec4::Y);
+        assert!(looking.biquat.is_finite());
+        assert!(looking.biquat.is_normalized());
+        assert!((looking * -Vec4::Z).abs_diff_eq(transform * Vec4::Y, 1e-4));
+    }
+
+    #[test]
+    fn rotate_keeps_position() {
+        let mut transform = Transform::from_translation(Vec3::new(1.0, 0.5, -0.3));
+        let position = transform * Vec4::W;
+        transform.rotate(Quat::from_rotation_y(0.7));
+        assert!((transform * Vec4::W).abs_diff_eq(position, 1e-5));
+        assert!(transform
+            .compute_matrix()
+            .abs_diff_eq(Mat4::from(transform.biquat), 1e-6));
+    }
+}
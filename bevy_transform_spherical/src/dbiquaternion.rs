@@ -0,0 +1,232 @@
+use std::ops::*;
+
+use bevy_math::{DMat4, DQuat, DVec4};
+
+use crate::biquaternion::Biquaternion;
+
+/// A double-precision [`Biquaternion`].
+/// This is the rotation taking the vector `v`
+/// to `DVec4::from(left * DQuat::from(v) * right)`.
+///
+/// Use this when many small rotations are composed, and `f32` rounding error would build up.
+/// Convert to a [`Biquaternion`] with [`DBiquaternion::as_f32`] before uploading to the GPU.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DBiquaternion {
+    pub left: DQuat,
+    pub right: DQuat,
+}
+
+impl DBiquaternion {
+    pub const IDENTITY: Self = Self {
+        left: DQuat::IDENTITY,
+        right: DQuat::IDENTITY,
+    };
+
+    #[inline(always)]
+    pub fn from_rotation(q: DQuat) -> Self {
+        Self {
+            left: q,
+            right: q.conjugate(),
+        }
+    }
+
+    /// Returns the biquaternion conjugate of `self`. For a unit biquaternion the
+    /// conjugate is also the inverse.
+    #[inline(always)]
+    pub fn conjugate(self) -> Self {
+        Self {
+            left: self.left.conjugate(),
+            right: self.right.conjugate(),
+        }
+    }
+
+    /// Returns the inverse of a normalized biquaternion.
+    /// Because `self` is assumed to already be unit length,
+    /// this method does not normalize before returning the conjugate.
+    #[inline(always)]
+    pub fn inverse(self) -> Self {
+        Self {
+            left: self.left.inverse(),
+            right: self.right.inverse(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn lerp(self, end: Self, s: f64) -> Self {
+        Self {
+            left: self.left.lerp(end.left, s),
+            right: self.right.lerp(end.right, s),
+        }
+    }
+
+    #[inline(always)]
+    pub fn slerp(self, end: Self, s: f64) -> Self {
+        Self {
+            left: self.left.slerp(end.left, s),
+            right: self.right.slerp(end.right, s),
+        }
+    }
+
+    /// Normalizes both quaternions in `self`.
+    #[inline(always)]
+    pub fn normalize(self) -> Self {
+        Self {
+            left: self.left.normalize(),
+            right: self.right.normalize(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_finite(self) -> bool {
+        self.left.is_finite() && self.right.is_finite()
+    }
+    #[inline(always)]
+    pub fn is_nan(self) -> bool {
+        self.left.is_nan() || self.right.is_nan()
+    }
+    #[inline(always)]
+    pub fn is_near_identity(self) -> bool {
+        self.left.is_near_identity() && self.right.is_near_identity()
+    }
+    /// Returns whether both quaternions in `self` are normalized.
+    #[inline(always)]
+    pub fn is_normalized(self) -> bool {
+        self.left.is_normalized() && self.right.is_normalized()
+    }
+
+    /// Returns true if the absolute difference of all elements between `self` and `other`
+    /// is less than or equal to `max_abs_diff`.
+    #[inline(always)]
+    pub fn abs_diff_eq(self, other: Self, max_abs_diff: f64) -> bool {
+        self.left.abs_diff_eq(other.left, max_abs_diff)
+            && self.right.abs_diff_eq(other.right, max_abs_diff)
+    }
+
+    /// Rounds `self` to single precision.
+    #[inline(always)]
+    pub fn as_f32(self) -> Biquaternion {
+        Biquaternion {
+            left: self.left.as_f32(),
+            right: self.right.as_f32(),
+        }
+    }
+}
+
+impl Biquaternion {
+    /// Converts `self` to double precision.
+    #[inline(always)]
+    pub fn as_f64(self) -> DBiquaternion {
+        DBiquaternion {
+            left: self.left.as_f64(),
+            right: self.right.as_f64(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl From<DBiquaternion> for DMat4 {
+    fn from(bq: DBiquaternion) -> DMat4 {
+        DMat4::from_cols(bq * DVec4::X, bq * DVec4::Y, bq * DVec4::Z, bq * DVec4::W)
+    }
+}
+
+impl From<Biquaternion> for DBiquaternion {
+    fn from(bq: Biquaternion) -> Self {
+        bq.as_f64()
+    }
+}
+
+impl From<DBiquaternion> for Biquaternion {
+    fn from(bq: DBiquaternion) -> Self {
+        bq.as_f32()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl Default for DBiquaternion {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Add for DBiquaternion {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            left: self.left + rhs.left,
+            right: self.right + rhs.right,
+        }
+    }
+}
+
+impl Sub for DBiquaternion {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            left: self.left - rhs.left,
+            right: self.right - rhs.right,
+        }
+    }
+}
+
+impl Neg for DBiquaternion {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            left: -self.left,
+            right: -self.right,
+        }
+    }
+}
+
+impl Mul for DBiquaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            left: self.left * rhs.left,
+            right: rhs.right * self.right,
+        }
+    }
+}
+
+impl Mul<DVec4> for DBiquaternion {
+    type Output = DVec4;
+
+    fn mul(self, rhs: DVec4) -> DVec4 {
+        DVec4::from(self.left * DQuat::from(rhs) * self.right)
+    }
+}
+
+impl Mul<f64> for DBiquaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self {
+        Self {
+            left: self.left * rhs,
+            right: self.right * rhs,
+        }
+    }
+}
+
+impl Div<f64> for DBiquaternion {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self {
+        Self {
+            left: self.left / rhs,
+            right: self.right / rhs,
+        }
+    }
+}
+
+impl MulAssign for DBiquaternion {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
@@ -0,0 +1,272 @@
+use bevy_math::{Quat, Vec4};
+
+use crate::biquaternion::Biquaternion;
+
+/// The length of the shortest geodesic from `a` to `b`, between 0 and π.
+/// Points are unit `Vec4`s, such as [`GlobalTransform::position`](crate::components::GlobalTransform::position).
+/// Multiply by the [`UniverseRadius`](crate::universe::UniverseRadius) to get world units.
+///
+/// Unlike the chord `(a - b).length()`, this is the distance measured inside the universe.
+#[inline]
+pub fn distance(a: Vec4, b: Vec4) -> f32 {
+    // More accurate than `acos(a.dot(b))` for nearby or antipodal points.
+    (a - b).length().atan2((a + b).length()) * 2.
+}
+
+/// The angle at `a` between the geodesics from `a` to `b` and from `a` to `c`, between 0 and π.
+///
+/// Returns 0 if `b` or `c` is `a` or its antipode, where the direction is undefined.
+#[inline]
+pub fn angle_at(a: Vec4, b: Vec4, c: Vec4) -> f32 {
+    let u = b - a * a.dot(b);
+    let v = c - a * a.dot(c);
+    let cross = (u.length_squared() * v.length_squared() - u.dot(v).powi(2))
+        .max(0.)
+        .sqrt();
+    cross.atan2(u.dot(v))
+}
+
+/// The point halfway along the shortest geodesic from `a` to `b`.
+///
+/// If `a` and `b` are antipodal, every point at distance π/2 is a midpoint;
+/// this returns one of them.
+#[inline]
+pub fn midpoint(a: Vec4, b: Vec4) -> Vec4 {
+    let sum = a + b;
+    if sum.length_squared() < 1e-12 {
+        any_orthogonal(a)
+    } else {
+        sum.normalize()
+    }
+}
+
+/// The point a fraction `t` of the way along the shortest geodesic from `a` to `b`.
+///
+/// `t = 0` gives `a`, and `t = 1` gives `b`.
+/// Values outside `0..=1` continue along the same great circle.
+/// If `a` and `b` are antipodal, this follows one of the many shortest geodesics.
+#[inline]
+pub fn geodesic_point(a: Vec4, b: Vec4, t: f32) -> Vec4 {
+    let angle = distance(a, b);
+    let sin = angle.sin();
+    if angle < 0.0001 {
+        a.lerp(b, t).normalize()
+    } else if sin < 0.0001 {
+        // Antipodal; go by way of a midpoint.
+        let m = any_orthogonal(a);
+        let angle = std::f32::consts::PI * t;
+        a * angle.cos() + m * angle.sin()
+    } else {
+        (a * ((1. - t) * angle).sin() + b * (t * angle).sin()) / sin
+    }
+}
+
+/// Follows the geodesic leaving `point` with initial velocity `tangent` for unit time.
+///
+/// Only the part of `tangent` orthogonal to `point` is used,
+/// so any `Vec4` may be passed in.
+#[inline]
+pub fn exp(point: Vec4, tangent: Vec4) -> Vec4 {
+    let tangent = tangent - point * point.dot(tangent);
+    let angle = tangent.length();
+    if angle < 0.0001 {
+        (point + tangent).normalize()
+    } else {
+        point * angle.cos() + tangent * (angle.sin() / angle)
+    }
+}
+
+/// The inverse of [`exp`]: the velocity at `point` of the shortest geodesic
+/// reaching `target` in unit time.
+///
+/// The result is orthogonal to `point`, and its length is [`distance(point, target)`](distance).
+/// If `target` is the antipode of `point`, every direction is shortest, and this returns zero.
+#[inline]
+pub fn log(point: Vec4, target: Vec4) -> Vec4 {
+    let cos = point.dot(target);
+    let tangent = target - point * cos;
+    let sin = tangent.length();
+    if sin < 0.0001 && cos > 0. {
+        tangent
+    } else if sin > 0. {
+        tangent * (sin.atan2(cos) / sin)
+    } else {
+        Vec4::ZERO
+    }
+}
+
+/// The isometry that slides `from` to `to` along the shortest geodesic between them,
+/// rotating only in the plane of that geodesic.
+///
+/// This is [`Transform::from_translation`](crate::components::Transform::from_translation)
+/// moved to start at `from`: reading points as quaternions, it is
+/// `v ↦ sqrt(to * from⁻¹) * v * sqrt(from⁻¹ * to)`.
+/// Applying it to a tangent vector at `from` parallel transports it to `to`.
+///
+/// If `to` is the antipode of `from`, every geodesic between them is shortest, and this picks one.
+#[inline]
+pub fn transvection(from: Vec4, to: Vec4) -> Biquaternion {
+    let from = Quat::from(from);
+    let to = Quat::from(to);
+    let left = quat_sqrt(to * from.conjugate());
+    Biquaternion {
+        left,
+        right: from.conjugate() * left * from,
+    }
+}
+
+/// Parallel transports the tangent vector `v` at `from` along the shortest geodesic to `to`.
+///
+/// The result is tangent at `to`, and has the same length as `v`.
+/// Copying `v` unchanged would not be tangent at `to`.
+#[inline]
+pub fn parallel_transport(from: Vec4, to: Vec4, v: Vec4) -> Vec4 {
+    transvection(from, to) * v
+}
+
+/// Parallel transports the frame `frame`, such as a
+/// [`GlobalTransform::biquat`](crate::components::GlobalTransform::biquat),
+/// along the shortest geodesic from its position to `to`.
+///
+/// Each local axis of the result is the parallel transport of the same local axis of `frame`.
+#[inline]
+pub fn parallel_transport_frame(frame: Biquaternion, to: Vec4) -> Biquaternion {
+    (transvection(frame * Vec4::W, to) * frame).normalize()
+}
+
+/// The square root of a unit quaternion, with nonnegative real part.
+#[inline]
+fn quat_sqrt(q: Quat) -> Quat {
+    let sum = q + Quat::IDENTITY;
+    if sum.length_squared() < 1e-8 {
+        // `q` is -1, whose square roots are all the pure unit quaternions.
+        Quat::from_xyzw(1., 0., 0., 0.)
+    } else {
+        sum.normalize()
+    }
+}
+
+/// The angle excess of the geodesic triangle `abc`:
+/// the sum of its angles minus π.
+///
+/// The triangle lies in a great 2-sphere, which has curvature 1,
+/// so this is positive for every nondegenerate triangle.
+#[inline]
+pub fn triangle_excess(a: Vec4, b: Vec4, c: Vec4) -> f32 {
+    angle_at(a, b, c) + angle_at(b, c, a) + angle_at(c, a, b) - std::f32::consts::PI
+}
+
+/// The area of the geodesic triangle `abc`.
+///
+/// By the Gauss-Bonnet theorem this equals [`triangle_excess`], because the curvature is 1.
+/// Multiply by the square of the [`UniverseRadius`](crate::universe::UniverseRadius)
+/// to get world units.
+#[inline]
+pub fn triangle_area(a: Vec4, b: Vec4, c: Vec4) -> f32 {
+    triangle_excess(a, b, c)
+}
+
+/// Some unit vector orthogonal to `v`.
+#[inline]
+pub(crate) fn any_orthogonal(v: Vec4) -> Vec4 {
+    // Rotate the larger of the `xy` and `zw` parts by a quarter turn.
+    let candidate = if v.x * v.x + v.y * v.y > v.z * v.z + v.w * v.w {
+        Vec4::new(-v.y, v.x, 0., 0.)
+    } else {
+        Vec4::new(0., 0., -v.w, v.z)
+    };
+    candidate.normalize()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::components::{GlobalTransform, Transform};
+    use bevy_math::Vec3;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn distance_along_translation() {
+        for &length in &[0.0, 1e-4, 0.5, 2.0, PI - 1e-3] {
+            let b = Transform::from_translation(Vec3::new(0.6, 0.0, 0.8) * length) * Vec4::W;
+            assert!((distance(Vec4::W, b) - length).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn geodesic_points() {
+        let a = Transform::from_translation(Vec3::new(0.2, -0.3, 0.1)) * Vec4::W;
+        let b = Transform::from_translation(Vec3::new(-1.0, 0.4, 0.9)) * Vec4::W;
+        let d = distance(a, b);
+        for &t in &[0.0, 0.25, 0.5, 1.0] {
+            let p = geodesic_point(a, b, t);
+            assert!((p.length() - 1.).abs() < 1e-5);
+            assert!((distance(a, p) - t * d).abs() < 1e-5);
+            assert!((distance(p, b) - (1. - t) * d).abs() < 1e-5);
+        }
+        assert!(midpoint(a, b).abs_diff_eq(geodesic_point(a, b, 0.5), 1e-5));
+
+        let m = midpoint(Vec4::X, -Vec4::X);
+        assert!((distance(Vec4::X, m) - FRAC_PI_2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn exp_log_round_trip() {
+        let point = Transform::from_translation(Vec3::new(0.4, 0.1, -0.7)) * Vec4::W;
+        for &translation in &[
+            Vec3::new(1e-5, 0., 0.),
+            Vec3::new(-0.3, 0.2, 1.1),
+            Vec3::new(0., 3.0, 0.),
+        ] {
+            let target = Transform::from_translation(translation) * point;
+            let tangent = log(point, target);
+            assert!(tangent.dot(point).abs() < 1e-5);
+            assert!((tangent.length() - distance(point, target)).abs() < 1e-5);
+            assert!(exp(point, tangent).abs_diff_eq(target, 1e-5));
+        }
+    }
+
+    #[test]
+    fn transport() {
+        let from = Transform::from_translation(Vec3::new(0.3, -0.2, 0.5)) * Vec4::W;
+        let to = Transform::from_translation(Vec3::new(-1.2, 0.4, 0.1)) * Vec4::W;
+        let v = GlobalTransform::from_translation(Vec3::new(0.3, -0.2, 0.5))
+            .local_tangent(Vec3::new(0.5, 1.0, -0.25));
+
+        let transported = parallel_transport(from, to, v);
+        assert!(transported.dot(to).abs() < 1e-5);
+        assert!((transported.length() - v.length()).abs() < 1e-5);
+
+        // The velocity of the geodesic is carried to its velocity at the far end.
+        let velocity = log(from, to);
+        assert!(parallel_transport(from, to, velocity).abs_diff_eq(-log(to, from), 1e-4));
+
+        // Starting from the origin, this is the usual translation.
+        let translation = Vec3::new(0.7, 0.0, -0.4);
+        let target = Transform::from_translation(translation);
+        assert!(transvection(Vec4::W, target * Vec4::W).abs_diff_eq(target.biquat, 1e-5));
+
+        // Antipodes still give a valid isometry.
+        let flip = transvection(from, -from);
+        assert!((flip * from).abs_diff_eq(-from, 1e-5));
+        assert!((flip * v).dot(from).abs() < 1e-5);
+
+        let frame = parallel_transport_frame(Transform::from_translation(Vec3::X).biquat, to);
+        assert!((frame * Vec4::W).abs_diff_eq(to, 1e-5));
+    }
+
+    #[test]
+    fn octant_triangle() {
+        // Three mutually orthogonal points bound an eighth of a great 2-sphere.
+        let (a, b, c) = (Vec4::X, Vec4::Y, Vec4::W);
+        assert!((angle_at(a, b, c) - FRAC_PI_2).abs() < 1e-6);
+        assert!((triangle_area(a, b, c) - FRAC_PI_2).abs() < 1e-5);
+
+        // Small triangles are nearly flat.
+        let rotation = Transform::from_rotation(Quat::from_rotation_z(0.3));
+        let b = Transform::from_translation(Vec3::X * 0.01) * Vec4::W;
+        let c = rotation * b;
+        assert!((angle_at(Vec4::W, b, c) - 0.3).abs() < 1e-4);
+        assert!(triangle_excess(Vec4::W, b, c).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,270 @@
+use bevy_app::prelude::*;
+use bevy_core::{FixedTimestep, FixedTimesteps};
+use bevy_ecs::{
+    entity::Entity,
+    query::Without,
+    schedule::{ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion, SystemStage},
+    system::{IntoExclusiveSystem, IntoSystem, Query, Res, ResMut},
+    world::World,
+};
+
+use crate::{
+    biquaternion::Biquaternion,
+    components::{Children, GlobalTransform, LocalScale, Parent, PreviousTransform, Transform},
+    InterpolationSystem, TransformSystem,
+};
+
+/// The stage in which fixed-timestep simulation systems run,
+/// added after [`CoreStage::Update`] by the [`FixedTimestepPlugin`].
+///
+/// It may run several times in one frame, or not at all.
+/// Each run first copies every [`Transform`] into its entity's [`PreviousTransform`].
+pub const FIXED_UPDATE: &str = "fixed_update";
+
+/// The label of the [`FixedTimestep`](bevy_core::FixedTimestep) driving [`FIXED_UPDATE`].
+pub const TRANSFORM_TIMESTEP: &str = "transform_timestep";
+
+/// Adds the [`FIXED_UPDATE`] stage, which runs [`FixedTimestepPlugin::timestep`] seconds apart,
+/// so that each root entity with a [`PreviousTransform`] is drawn between its last two states
+/// in that stage, by [`transform_interpolate_system`]. See [`PreviousTransform`] for details.
+///
+/// Add it after [`TransformPlugin`](crate::TransformPlugin).
+#[derive(Debug, Clone, Copy)]
+pub struct FixedTimestepPlugin {
+    /// The simulated time between runs of [`FIXED_UPDATE`], in seconds.
+    pub timestep: f64,
+}
+
+impl Plugin for FixedTimestepPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_stage_after(
+            CoreStage::Update,
+            FIXED_UPDATE,
+            SystemStage::parallel()
+                .with_run_criteria(
+                    FixedTimestep::step(self.timestep).with_label(TRANSFORM_TIMESTEP),
+                )
+                .with_system(previous_transform_system.exclusive_system().at_start()),
+        )
+        .init_resource::<InterpolationFactor>()
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            interpolation_factor_system
+                .system()
+                .before(InterpolationSystem::TransformInterpolate),
+        )
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            transform_interpolate_system
+                .system()
+                .label(InterpolationSystem::TransformInterpolate)
+                .after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// How far rendering has got from the previous simulation state toward the current one,
+/// between 0 and 1.
+///
+/// Inserted by the [`FixedTimestepPlugin`], whose [`interpolation_factor_system`] sets this
+/// from the [`TRANSFORM_TIMESTEP`] every frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InterpolationFactor(pub f32);
+
+impl Default for InterpolationFactor {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+/// Copies each [`Transform`] into its [`PreviousTransform`], before a simulation step changes it.
+/// Runs at the start of every [`FIXED_UPDATE`].
+pub fn previous_transform_system(world: &mut World) {
+    for (transform, mut previous) in world
+        .query::<(&Transform, &mut PreviousTransform)>()
+        .iter_mut(world)
+    {
+        previous.0 = *transform;
+    }
+}
+
+/// Sets the [`InterpolationFactor`] from how far the [`TRANSFORM_TIMESTEP`] has got
+/// toward its next step.
+pub fn interpolation_factor_system(
+    timesteps: Option<Res<FixedTimesteps>>,
+    mut factor: ResMut<InterpolationFactor>,
+) {
+    let overstep = timesteps
+        .as_ref()
+        .and_then(|timesteps| timesteps.get(TRANSFORM_TIMESTEP))
+        .map_or(1., |state| state.overstep_percentage() as f32)
+        .min(1.);
+    if factor.0 != overstep {
+        factor.0 = overstep;
+    }
+}
+
+/// Draws each root entity with a [`PreviousTransform`] partway between its previous and
+/// current [`Transform`], by [`Biquaternion::slerp`] with the [`InterpolationFactor`].
+///
+/// This runs after the transforms are propagated, and moves the [`GlobalTransform`]s of the
+/// entity's descendants along with it, so that they stay put relative to the entity.
+/// The [`Transform`]s are not changed, so the simulation never sees interpolated states.
+#[allow(clippy::type_complexity)]
+pub fn transform_interpolate_system(
+    factor: Res<InterpolationFactor>,
+    mut root_query: Query<
+        (
+            Entity,
+            &Transform,
+            &PreviousTransform,
+            Option<&LocalScale>,
+            Option<&Children>,
+        ),
+        Without<Parent>,
+    >,
+    mut global_query: Query<&mut GlobalTransform>,
+    children_query: Query<&Children>,
+) {
+    for (entity, transform, previous, scale, children) in root_query.iter_mut() {
+        let interpolated = interpolate(previous.0.biquat, transform.biquat, factor.0);
+        let mut global_transform = match global_query.get_mut(entity) {
+            Ok(global_transform) => global_transform,
+            Err(_) => continue,
+        };
+        let scale = scale.map_or(1., |scale| scale.0);
+        if global_transform.biquat == interpolated && global_transform.scale == scale {
+            continue;
+        }
+        // The globals in the hierarchy are all relative to whatever the root has now,
+        // which may be last frame's interpolation, so move them by the difference.
+        let difference = interpolated * global_transform.biquat.inverse();
+        *global_transform = GlobalTransform {
+            biquat: interpolated,
+            scale,
+        };
+
+        if let Some(children) = children {
+            for &child in children.iter() {
+                move_recursive(difference, &mut global_query, &children_query, child);
+            }
+        }
+    }
+}
+
+fn move_recursive(
+    difference: Biquaternion,
+    global_query: &mut Query<&mut GlobalTransform>,
+    children_query: &Query<&Children>,
+    entity: Entity,
+) {
+    if let Ok(mut global_transform) = global_query.get_mut(entity) {
+        global_transform.biquat = (difference * global_transform.biquat).normalize();
+    } else {
+        return;
+    }
+    if let Ok(children) = children_query.get(entity) {
+        for &child in children.iter() {
+            move_recursive(difference, global_query, children_query, child);
+        }
+    }
+}
+
+/// Slerps from `start` to whichever of `end` and `-end` is closer,
+/// since both are the same isometry.
+#[inline]
+fn interpolate(start: Biquaternion, end: Biquaternion, s: f32) -> Biquaternion {
+    if s >= 1. {
+        return end;
+    }
+    let end = if start.left.dot(end.left) + start.right.dot(end.right) < 0. {
+        -end
+    } else {
+        end
+    };
+    start.slerp(end, s).normalize()
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{
+        schedule::{Schedule, Stage, SystemStage},
+        system::IntoSystem,
+    };
+    use bevy_math::{Quat, Vec3, Vec4};
+
+    use super::*;
+    use crate::hierarchy::BuildWorldChildren;
+
+    #[test]
+    fn interpolates_between_steps() {
+        let mut world = World::default();
+        world.insert_resource(InterpolationFactor(0.25));
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(transform_interpolate_system.system());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", update_stage);
+
+        let previous = Transform::from_translation(Vec3::new(0.2, 0.0, -0.1));
+        let current = Transform::from_translation(Vec3::new(-0.4, 0.3, 0.5))
+            * Transform::from_rotation(Quat::from_rotation_y(0.8));
+        let offset = Transform::from_translation(Vec3::new(0.0, 0.1, 0.0));
+        let mut child = None;
+        let root = world
+            .spawn()
+            .insert_bundle((
+                // The other representative of the same isometry.
+                Transform {
+                    biquat: -current.biquat,
+                },
+                PreviousTransform(previous),
+                GlobalTransform::from(current),
+            ))
+            .with_children(|parent| {
+                child = Some(
+                    parent
+                        .spawn_bundle((offset, GlobalTransform::from(current) * offset))
+                        .id(),
+                );
+            })
+            .id();
+        let child = child.unwrap();
+        schedule.run(&mut world);
+
+        // A quarter of the way along, taking the short way around despite the sign flip.
+        let global = *world.get::<GlobalTransform>(root).unwrap();
+        let expected = previous.biquat.slerp(current.biquat, 0.25).normalize();
+        assert!(global.biquat.abs_diff_eq(expected, 1e-5));
+        let child_global = *world.get::<GlobalTransform>(child).unwrap();
+        assert!((child_global.biquat).abs_diff_eq((global * offset).biquat, 1e-5));
+
+        // Running again with the same factor changes nothing.
+        schedule.run(&mut world);
+        assert_eq!(*world.get::<GlobalTransform>(root).unwrap(), global);
+        assert!(world
+            .get::<GlobalTransform>(child)
+            .unwrap()
+            .biquat
+            .abs_diff_eq(child_global.biquat, 1e-5));
+
+        // At the end of the step, the current state is drawn.
+        world.insert_resource(InterpolationFactor(1.));
+        schedule.run(&mut world);
+        let global = *world.get::<GlobalTransform>(root).unwrap();
+        assert!(global.position().abs_diff_eq(current * Vec4::W, 1e-5));
+        assert!(world
+            .get::<GlobalTransform>(child)
+            .unwrap()
+            .position()
+            .abs_diff_eq((current * offset) * Vec4::W, 1e-5));
+
+        // A new scale is picked up even when the root has not moved.
+        world.entity_mut(root).insert(LocalScale(2.));
+        schedule.run(&mut world);
+        let scaled = *world.get::<GlobalTransform>(root).unwrap();
+        assert_eq!(scaled.biquat, global.biquat);
+        assert_eq!(scaled.scale, 2.);
+    }
+}
@@ -1,27 +1,90 @@
+pub mod animation;
 pub mod biquaternion;
+pub mod bivector;
+pub mod charts;
+pub mod complex_quaternion;
 pub mod components;
+pub mod dbiquaternion;
+pub mod geometry;
+pub mod interpolation;
+pub mod quantize;
+pub mod quotient;
+pub mod renormalize;
+pub mod rotation_set;
+pub mod space;
+pub mod spline;
 pub use ::bevy_transform::hierarchy;
 pub mod transform_propagate_system;
+pub mod universe;
 pub use bevy_transform::TransformSystem;
 
 pub mod prelude {
-    pub use crate::{components::*, hierarchy::*, TransformPlugin};
+    #[cfg(feature = "animation")]
+    pub use crate::animation::{AnimationPlayer, AnimationPlugin};
+    pub use crate::{
+        animation::{AnimationClip, Keyframe, KeyframeInterpolation},
+        components::*,
+        hierarchy::*,
+        interpolation::{FixedTimestepPlugin, InterpolationFactor},
+        quotient::QuotientGroup,
+        renormalize::{TransformDrift, TransformDriftDiagnosticsPlugin},
+        space::{Elliptic, Euclidean, Geometry, Hyperbolic, Spherical},
+        spline::{Spline, SplineFollower, SplinePlugin},
+        universe::{SpaceForm, UniverseRadius},
+        TransformPlugin,
+    };
 }
 
 use bevy_app::prelude::*;
-use bevy_ecs::{schedule::ParallelSystemDescriptorCoercion, system::IntoSystem};
-use prelude::{parent_update_system, Children, GlobalTransform, Parent, PreviousParent, Transform};
+use bevy_ecs::{
+    schedule::{ParallelSystemDescriptorCoercion, SystemLabel},
+    system::IntoSystem,
+};
+use biquaternion::Biquaternion;
+use prelude::{
+    parent_update_system, Children, DTransform, GlobalTransform, HyperbolicGlobalTransform,
+    HyperbolicTransform, LocalScale, Parent, PreviousParent, PreviousTransform, SpaceForm,
+    Transform, TransformDrift, UniverseRadius,
+};
+use renormalize::transform_renormalize_system;
+use transform_propagate_system::{dglobal_transform_sync_system, transform_propagate_system};
+
+/// Labels for the systems that propagate [`DTransform`]s.
+/// Everything labelled [`TransformSystem::TransformPropagate`] runs after these,
+/// so the propagated transforms are ready at the same point as single-precision ones.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum DTransformSystem {
+    /// Propagates [`DTransform`] to [`DGlobalTransform`](prelude::DGlobalTransform).
+    TransformPropagate,
+}
+
+/// Labels for the systems that draw entities between fixed simulation steps.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum InterpolationSystem {
+    /// Moves [`GlobalTransform`]s between the [`PreviousTransform`] and the [`Transform`].
+    /// Runs after [`TransformSystem::TransformPropagate`], and only if the
+    /// [`FixedTimestepPlugin`](interpolation::FixedTimestepPlugin) is added.
+    TransformInterpolate,
+}
 
 #[derive(Default)]
 pub struct TransformPlugin;
 
 impl Plugin for TransformPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.register_type::<Children>()
+        app.init_resource::<UniverseRadius>()
+            .init_resource::<SpaceForm>()
+            .init_resource::<TransformDrift>()
+            .register_type::<Children>()
             .register_type::<Parent>()
             .register_type::<PreviousParent>()
+            .register_type::<Biquaternion>()
             .register_type::<Transform>()
             .register_type::<GlobalTransform>()
+            .register_type::<LocalScale>()
+            .register_type::<PreviousTransform>()
+            .register_type::<HyperbolicTransform>()
+            .register_type::<HyperbolicGlobalTransform>()
             // add transform systems to startup so the first update is "correct"
             .add_startup_system_to_stage(
                 StartupStage::PostStartup,
@@ -31,11 +94,40 @@ impl Plugin for TransformPlugin {
             )
             .add_startup_system_to_stage(
                 StartupStage::PostStartup,
-                transform_propagate_system::transform_propagate_system
+                transform_propagate_system::<Transform>
                     .system()
                     .label(TransformSystem::TransformPropagate)
+                    .after(TransformSystem::ParentUpdate)
+                    .after(DTransformSystem::TransformPropagate),
+            )
+            .add_startup_system_to_stage(
+                StartupStage::PostStartup,
+                transform_propagate_system::<DTransform>
+                    .system()
+                    .label(DTransformSystem::TransformPropagate)
                     .after(TransformSystem::ParentUpdate),
             )
+            .add_startup_system_to_stage(
+                StartupStage::PostStartup,
+                transform_propagate_system::<HyperbolicTransform>
+                    .system()
+                    .label(TransformSystem::TransformPropagate)
+                    .after(TransformSystem::ParentUpdate)
+                    .after(DTransformSystem::TransformPropagate),
+            )
+            .add_startup_system_to_stage(
+                StartupStage::PostStartup,
+                dglobal_transform_sync_system
+                    .system()
+                    .label(TransformSystem::TransformPropagate)
+                    .after(DTransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                transform_renormalize_system
+                    .system()
+                    .before(TransformSystem::TransformPropagate),
+            )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 parent_update_system
@@ -44,10 +136,33 @@ impl Plugin for TransformPlugin {
             )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
-                transform_propagate_system::transform_propagate_system
+                transform_propagate_system::<Transform>
                     .system()
                     .label(TransformSystem::TransformPropagate)
+                    .after(TransformSystem::ParentUpdate)
+                    .after(DTransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                transform_propagate_system::<DTransform>
+                    .system()
+                    .label(DTransformSystem::TransformPropagate)
                     .after(TransformSystem::ParentUpdate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                transform_propagate_system::<HyperbolicTransform>
+                    .system()
+                    .label(TransformSystem::TransformPropagate)
+                    .after(TransformSystem::ParentUpdate)
+                    .after(DTransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                dglobal_transform_sync_system
+                    .system()
+                    .label(TransformSystem::TransformPropagate)
+                    .after(DTransformSystem::TransformPropagate),
             );
     }
 }
@@ -0,0 +1,210 @@
+use bevy_math::Quat;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+use crate::biquaternion::Biquaternion;
+
+/// The number of bits stored for each of the three smallest components of a quaternion.
+pub const QUANTIZED_COMPONENT_BITS: u32 = 15;
+
+/// A bound on how far a [`Biquaternion`] moves when it is quantized, in radians:
+/// no point of the unit 3-sphere moves further than this.
+///
+/// So the position of a quantized transform, and the direction of each of its local axes,
+/// is off by at most this much. Multiply by the
+/// [`UniverseRadius`](crate::universe::UniverseRadius) to get world units.
+///
+/// Each stored component is rounded by at most half a step of `√2 / (2¹⁵ - 1)`,
+/// which moves a quaternion by at most `2√3` times that, because its largest component,
+/// the one left out, is at least 1/2. Each quaternion moves points by as much as it moves,
+/// and there are two.
+pub const MAX_QUANTIZATION_ERROR: f32 = 1.5e-4;
+
+const COMPONENT_MAX: u32 = (1 << QUANTIZED_COMPONENT_BITS) - 1;
+/// The three smallest components of a unit quaternion lie between `-1/√2` and `1/√2`.
+const COMPONENT_RANGE: f32 = std::f32::consts::FRAC_1_SQRT_2;
+const QUAT_BITS: u32 = 2 + 3 * QUANTIZED_COMPONENT_BITS;
+
+/// A [`Biquaternion`] packed into 12 bytes, for save files and network replication.
+///
+/// Each quaternion is stored "smallest three" style: the index of its largest component,
+/// then its other three components, scaled so the largest one is positive,
+/// in [`QUANTIZED_COMPONENT_BITS`] bits each.
+/// Since `(left, right)` and `(-left, -right)` are the same rotation,
+/// one more bit, for the sign of `right`, is enough to recover both.
+///
+/// Decoding gives a normalized [`Biquaternion`] within [`MAX_QUANTIZATION_ERROR`] of the original.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct QuantizedBiquaternion(pub [u8; 12]);
+
+impl QuantizedBiquaternion {
+    /// Quantizes `biquat`, after normalizing it.
+    pub fn new(biquat: Biquaternion) -> Self {
+        let biquat = biquat.normalize();
+        let (left, left_negative) = encode_quat(biquat.left);
+        let (right, right_negative) = encode_quat(biquat.right);
+        // Negating both quaternions makes the largest component of `left` positive.
+        let negate_right = left_negative != right_negative;
+        let packed =
+            left as u128 | (right as u128) << QUAT_BITS | (negate_right as u128) << (2 * QUAT_BITS);
+
+        let mut bytes = [0; 12];
+        bytes.copy_from_slice(&packed.to_le_bytes()[..12]);
+        Self(bytes)
+    }
+
+    /// The [`Biquaternion`] that was quantized, to within [`MAX_QUANTIZATION_ERROR`].
+    pub fn dequantize(self) -> Biquaternion {
+        let mut bytes = [0; 16];
+        bytes[..12].copy_from_slice(&self.0);
+        let packed = u128::from_le_bytes(bytes);
+        let mask = (1 << QUAT_BITS) - 1;
+
+        let left = decode_quat((packed & mask) as u64);
+        let right = decode_quat((packed >> QUAT_BITS & mask) as u64);
+        let negate_right = packed >> (2 * QUAT_BITS) & 1 == 1;
+        Biquaternion {
+            left,
+            right: if negate_right { -right } else { right },
+        }
+    }
+}
+
+impl From<Biquaternion> for QuantizedBiquaternion {
+    fn from(biquat: Biquaternion) -> Self {
+        Self::new(biquat)
+    }
+}
+
+impl From<QuantizedBiquaternion> for Biquaternion {
+    fn from(quantized: QuantizedBiquaternion) -> Self {
+        quantized.dequantize()
+    }
+}
+
+/// Packs the unit quaternion `q` into [`QUAT_BITS`] bits,
+/// and returns whether its largest component was negative, and so was negated.
+fn encode_quat(q: Quat) -> (u64, bool) {
+    let components: [f32; 4] = q.into();
+    let largest = (1..4).fold(0, |largest, i| {
+        if components[i].abs() > components[largest].abs() {
+            i
+        } else {
+            largest
+        }
+    });
+    let negative = components[largest] < 0.;
+    let sign = if negative { -1. } else { 1. };
+
+    let mut bits = largest as u64;
+    for (_, &component) in components.iter().enumerate().filter(|&(i, _)| i != largest) {
+        let unit = ((component * sign / COMPONENT_RANGE).clamp(-1., 1.) + 1.) * 0.5;
+        bits = bits << QUANTIZED_COMPONENT_BITS | (unit * COMPONENT_MAX as f32).round() as u64;
+    }
+    (bits, negative)
+}
+
+/// Unpacks a quaternion packed by [`encode_quat`], with its largest component positive.
+fn decode_quat(mut bits: u64) -> Quat {
+    let mut components = [0.; 4];
+    let mut small = [0.; 3];
+    for value in small.iter_mut().rev() {
+        let unit = (bits & COMPONENT_MAX as u64) as f32 / COMPONENT_MAX as f32;
+        *value = (unit * 2. - 1.) * COMPONENT_RANGE;
+        bits >>= QUANTIZED_COMPONENT_BITS;
+    }
+    let largest = bits as usize;
+
+    let mut small = small.iter();
+    let mut length_squared = 0.;
+    for (i, component) in components.iter_mut().enumerate() {
+        if i != largest {
+            *component = *small.next().unwrap();
+            length_squared += *component * *component;
+        }
+    }
+    components[largest] = (1. - length_squared).max(0.).sqrt();
+    Quat::from_xyzw(components[0], components[1], components[2], components[3]).normalize()
+}
+
+impl Biquaternion {
+    /// Packs `self` into 12 bytes. See [`QuantizedBiquaternion`].
+    #[inline]
+    pub fn quantize(self) -> QuantizedBiquaternion {
+        QuantizedBiquaternion::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{components::Transform, geometry::distance};
+    use bevy_math::{Vec3, Vec4};
+
+    /// The distance between the positions of `a` and `b`,
+    /// and the largest angle between their corresponding local axes.
+    fn error(a: Biquaternion, b: Biquaternion) -> (f32, f32) {
+        let position = distance(a * Vec4::W, b * Vec4::W);
+        let angle = [Vec4::X, Vec4::Y, Vec4::Z]
+            .iter()
+            .map(|&axis| distance(a * axis, b * axis))
+            .fold(0., f32::max);
+        (position, angle)
+    }
+
+    #[test]
+    fn quantization_error_is_bounded() {
+        // A deterministic spread of transforms, from a linear congruential generator.
+        let mut state = 0x2545_f491_u32;
+        let mut random = move || {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 8) as f32 / (1 << 24) as f32 * 2. - 1.
+        };
+
+        let (mut max_position, mut max_angle) = (0f32, 0f32);
+        for _ in 0..10_000 {
+            let translation = Vec3::new(random(), random(), random()) * std::f32::consts::PI;
+            let axis = Vec3::new(random(), random(), random()).normalize();
+            let biquat = (Transform::from_translation(translation)
+                * Transform::from_rotation(Quat::from_axis_angle(axis, random() * 3.2)))
+            .biquat;
+
+            let round_trip = biquat.quantize().dequantize();
+            assert!(round_trip.is_normalized());
+            let (position, angle) = error(biquat, round_trip);
+            max_position = max_position.max(position);
+            max_angle = max_angle.max(angle);
+        }
+        assert!(max_position < MAX_QUANTIZATION_ERROR, "{}", max_position);
+        assert!(max_angle < MAX_QUANTIZATION_ERROR, "{}", max_angle);
+        // The bound is not far from what happens.
+        assert!(max_angle > MAX_QUANTIZATION_ERROR / 10., "{}", max_angle);
+    }
+
+    #[test]
+    fn quantize_edge_cases() {
+        for biquat in [
+            Biquaternion::IDENTITY,
+            -Biquaternion::IDENTITY,
+            // The antipodal map, which is not the identity.
+            Biquaternion {
+                left: Quat::IDENTITY,
+                right: -Quat::IDENTITY,
+            },
+            // Two equally large components.
+            Transform::from_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)).biquat,
+            Transform::from_translation(Vec3::X * std::f32::consts::PI).biquat,
+        ] {
+            let round_trip = biquat.quantize().dequantize();
+            let (position, angle) = error(biquat, round_trip);
+            assert!(position < MAX_QUANTIZATION_ERROR && angle < MAX_QUANTIZATION_ERROR);
+        }
+        // Unnormalized input is normalized first.
+        assert_eq!(
+            (Biquaternion::IDENTITY * 1.5).quantize(),
+            Biquaternion::IDENTITY.quantize()
+        );
+        assert_eq!(std::mem::size_of::<QuantizedBiquaternion>(), 12);
+    }
+}
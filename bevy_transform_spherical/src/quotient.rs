@@ -0,0 +1,400 @@
+use bevy_math::{Quat, Vec4};
+
+use crate::{
+    biquaternion::Biquaternion,
+    bivector::Bivector,
+    components::{GlobalTransform, Transform},
+    rotation_set::RotationSet,
+};
+
+/// A finite group Γ of isometries of S³, acting freely, so that the universe is the quotient S³/Γ.
+///
+/// Every point `p` of the quotient has one image `g * p` in S³ for each element `g`,
+/// and an object is drawn once at each image.
+/// The first element is always the identity, which maps each object to itself.
+///
+/// Insert this resource to turn on the quotient.
+/// The renderer spawns one image entity per extra element for every mesh entity;
+/// each image is sorted by its own distance, like any other entity.
+/// Use [`QuotientGroup::nearest_image`] to measure distances inside the quotient.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotientGroup {
+    elements: Vec<Biquaternion>,
+}
+
+impl QuotientGroup {
+    /// The trivial group, whose quotient is S³ itself.
+    pub fn trivial() -> Self {
+        Self {
+            elements: vec![Biquaternion::IDENTITY],
+        }
+    }
+
+    /// Creates the group generated by closing `generators` under multiplication.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group has more than `max_order` elements,
+    /// which happens if the generators do not generate a finite group.
+    pub fn generated_by(generators: &[Biquaternion], max_order: usize) -> Self {
+        let mut elements = RotationSet::new(SAME_ISOMETRY_EPSILON);
+        elements.insert(Biquaternion::IDENTITY);
+        let mut i = 0;
+        while i < elements.len() {
+            for &generator in generators {
+                let product = (elements.elements()[i] * generator).normalize();
+                if elements.insert(product) {
+                    assert!(
+                        elements.len() <= max_order,
+                        "the generators do not generate a group of order at most {}",
+                        max_order
+                    );
+                }
+            }
+            i += 1;
+        }
+        Self {
+            elements: elements.into(),
+        }
+    }
+
+    /// The cyclic group of order `p` generating the lens space L(p, q).
+    ///
+    /// Its generator rotates by 2π/p in the `xy` plane and by 2πq/p in the `zw` plane.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is 0, or if `p` and `q` are not coprime, since then the group does not act freely.
+    pub fn lens_space(p: u32, q: u32) -> Self {
+        assert!(p > 0, "the lens space L(p, q) needs p > 0");
+        assert!(
+            gcd(p, q) == 1,
+            "the lens space L({}, {}) needs p and q to be coprime",
+            p,
+            q
+        );
+        let elements = (0..p)
+            .map(|k| {
+                let angle = std::f32::consts::TAU * k as f32 / p as f32;
+                Biquaternion::exp(Bivector {
+                    xy: angle,
+                    zw: angle * q as f32,
+                    ..Bivector::ZERO
+                })
+            })
+            .collect();
+        Self { elements }
+    }
+
+    /// The binary tetrahedral group of order 24, acting by left multiplication.
+    pub fn binary_tetrahedral() -> Self {
+        Self::left_multiplication(binary_tetrahedral_quats())
+    }
+
+    /// The binary octahedral group of order 48, acting by left multiplication.
+    pub fn binary_octahedral() -> Self {
+        let mut quats = binary_tetrahedral_quats();
+        let s = std::f32::consts::FRAC_1_SQRT_2;
+        for a in 0..4 {
+            for b in a + 1..4 {
+                for &(sa, sb) in &[(s, s), (s, -s), (-s, s), (-s, -s)] {
+                    let mut q = [0.; 4];
+                    q[a] = sa;
+                    q[b] = sb;
+                    quats.push(Quat::from_xyzw(q[0], q[1], q[2], q[3]));
+                }
+            }
+        }
+        Self::left_multiplication(quats)
+    }
+
+    /// The binary icosahedral group of order 120, acting by left multiplication.
+    /// The quotient is the Poincaré dodecahedral space, also known as the Poincaré homology sphere.
+    ///
+    /// The fundamental domain around `Vec4::W` is a regular dodecahedron, and the space is
+    /// made by gluing each face to the opposite one with a twist of π/5.
+    /// Use [`QuotientGroup::cell`] and [`QuotientGroup::wrap`] to keep entities inside it.
+    pub fn poincare_dodecahedral() -> Self {
+        let mut quats = binary_tetrahedral_quats();
+        let phi = (1. + 5f32.sqrt()) / 2.;
+        // The even permutations of `(0, ±1, ±1/φ, ±φ) / 2`, in the order `(w, x, y, z)`.
+        let magnitudes = [0., 0.5, 0.5 / phi, 0.5 * phi];
+        for permutation in &EVEN_PERMUTATIONS {
+            for signs in 0..8 {
+                let mut q = [0.; 4];
+                for (i, &position) in permutation.iter().enumerate() {
+                    let sign = if i > 0 && signs & (1 << (i - 1)) != 0 {
+                        -1.
+                    } else {
+                        1.
+                    };
+                    q[position] = sign * magnitudes[i];
+                }
+                quats.push(Quat::from_xyzw(q[1], q[2], q[3], q[0]));
+            }
+        }
+        Self::left_multiplication(quats)
+    }
+
+    /// The group acting on S³ by multiplying on the left by each of the unit quaternions `quats`.
+    /// Left multiplication by a unit quaternion other than 1 has no fixed points,
+    /// so this always acts freely.
+    ///
+    /// The identity quaternion is moved to the front if present.
+    pub fn left_multiplication(quats: impl IntoIterator<Item = Quat>) -> Self {
+        let mut elements: Vec<_> = quats
+            .into_iter()
+            .map(|q| Biquaternion {
+                left: q,
+                right: Quat::IDENTITY,
+            })
+            .collect();
+        if let Some(i) = elements
+            .iter()
+            .position(|&e| e.approx_same_rotation(Biquaternion::IDENTITY, SAME_ISOMETRY_EPSILON))
+        {
+            elements.swap(0, i);
+        }
+        Self { elements }
+    }
+
+    /// The elements of the group, starting with the identity.
+    pub fn elements(&self) -> &[Biquaternion] {
+        &self.elements
+    }
+
+    /// The number of elements of the group.
+    pub fn order(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns the index of the cell of the tiling of S³ by copies of the fundamental domain
+    /// that contains `point`.
+    ///
+    /// The fundamental domain is the set of points at least as close to `Vec4::W`
+    /// as to any of its images; this is its Dirichlet domain.
+    /// Cell `i` is the image of the fundamental domain under `elements()[i]`,
+    /// so cell 0 is the fundamental domain itself.
+    pub fn cell(&self, point: Vec4) -> usize {
+        self.elements
+            .iter()
+            .map(|&g| (g * Vec4::W).dot(point))
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(0, |(i, _)| i)
+    }
+
+    /// Moves `transform` by a group element so that its position lies in the fundamental domain.
+    /// This does not change which point of the quotient it is at.
+    pub fn wrap(&self, transform: Transform) -> Transform {
+        let g = self.elements[self.cell(transform * Vec4::W)];
+        Transform {
+            biquat: (g.inverse() * transform.biquat).normalize(),
+        }
+    }
+
+    /// Returns the image of `point` closest to `from`.
+    pub fn nearest_image(&self, from: Vec4, point: Vec4) -> Vec4 {
+        self.elements
+            .iter()
+            .map(|&g| g * point)
+            .max_by(|a, b| a.dot(from).total_cmp(&b.dot(from)))
+            .unwrap_or(point)
+    }
+
+    /// Returns the image of `transform` whose position is closest to `from`.
+    pub fn nearest_image_transform(
+        &self,
+        from: Vec4,
+        transform: GlobalTransform,
+    ) -> GlobalTransform {
+        self.elements
+            .iter()
+            .map(|&g| GlobalTransform {
+                biquat: g * transform.biquat,
+                ..transform
+            })
+            .max_by(|a, b| {
+                let a = a.position().dot(from);
+                let b = b.position().dot(from);
+                a.total_cmp(&b)
+            })
+            .unwrap_or(transform)
+    }
+}
+
+impl Default for QuotientGroup {
+    fn default() -> Self {
+        Self::trivial()
+    }
+}
+
+/// The 12 even permutations of four things.
+const EVEN_PERMUTATIONS: [[usize; 4]; 12] = [
+    [0, 1, 2, 3],
+    [0, 2, 3, 1],
+    [0, 3, 1, 2],
+    [1, 0, 3, 2],
+    [1, 2, 0, 3],
+    [1, 3, 2, 0],
+    [2, 0, 1, 3],
+    [2, 1, 3, 0],
+    [2, 3, 0, 1],
+    [3, 0, 2, 1],
+    [3, 1, 0, 2],
+    [3, 2, 1, 0],
+];
+
+/// The 24 Hurwitz units `±1, ±i, ±j, ±k, (±1 ± i ± j ± k)/2`.
+fn binary_tetrahedral_quats() -> Vec<Quat> {
+    let mut quats = Vec::with_capacity(24);
+    quats.push(Quat::IDENTITY);
+    quats.push(-Quat::IDENTITY);
+    for &axis in &[Vec4::X, Vec4::Y, Vec4::Z] {
+        quats.push(Quat::from(axis));
+        quats.push(Quat::from(-axis));
+    }
+    for signs in 0..16 {
+        let sign = |bit: u32| if signs & (1 << bit) == 0 { 0.5 } else { -0.5 };
+        quats.push(Quat::from_xyzw(sign(0), sign(1), sign(2), sign(3)));
+    }
+    quats
+}
+
+/// How close two elements must be to count as the same isometry.
+const SAME_ISOMETRY_EPSILON: f32 = 1e-4;
+
+/// The greatest common divisor of `a` and `b`.
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy_math::Vec3;
+
+    fn is_closed(group: &QuotientGroup) -> bool {
+        group.elements().iter().all(|&a| {
+            group.elements().iter().all(|&b| {
+                let product = a * b;
+                group
+                    .elements()
+                    .iter()
+                    .any(|&e| e.approx_same_rotation(product, SAME_ISOMETRY_EPSILON))
+            })
+        })
+    }
+
+    #[test]
+    fn groups_are_closed() {
+        for (group, order) in [
+            (QuotientGroup::trivial(), 1),
+            (QuotientGroup::lens_space(5, 2), 5),
+            (QuotientGroup::binary_tetrahedral(), 24),
+            (QuotientGroup::binary_octahedral(), 48),
+            (QuotientGroup::poincare_dodecahedral(), 120),
+        ] {
+            assert_eq!(group.order(), order);
+            assert!(group.elements()[0].abs_diff_eq(Biquaternion::IDENTITY, 1e-6));
+            assert!(is_closed(&group));
+        }
+    }
+
+    #[test]
+    fn generated_matches_listed() {
+        let generated = QuotientGroup::generated_by(
+            &[
+                Biquaternion {
+                    left: Quat::from_xyzw(0.5, 0.5, 0.5, 0.5),
+                    right: Quat::IDENTITY,
+                },
+                Biquaternion {
+                    left: Quat::from_xyzw(1., 0., 0., 0.),
+                    right: Quat::IDENTITY,
+                },
+            ],
+            100,
+        );
+        assert_eq!(generated.order(), 24);
+
+        // Long products pile up rounding error, which must not split an element in two.
+        let phi = (1. + 5f32.sqrt()) / 2.;
+        let icosahedral = QuotientGroup::generated_by(
+            &[
+                Biquaternion {
+                    left: Quat::from_xyzw(0.5, 0.5, 0.5, 0.5),
+                    right: Quat::IDENTITY,
+                },
+                Biquaternion {
+                    left: Quat::from_xyzw(0.5 / phi, 0.5, 0., 0.5 * phi),
+                    right: Quat::IDENTITY,
+                },
+            ],
+            200,
+        );
+        assert_eq!(icosahedral.order(), 120);
+        assert!(is_closed(&icosahedral));
+    }
+
+    #[test]
+    #[should_panic]
+    fn lens_space_needs_positive_p() {
+        QuotientGroup::lens_space(0, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn lens_space_needs_coprime() {
+        QuotientGroup::lens_space(6, 4);
+    }
+
+    #[test]
+    fn nan_does_not_panic() {
+        let group = QuotientGroup::lens_space(3, 1);
+        let nan = Vec4::splat(f32::NAN);
+        group.cell(nan);
+        group.nearest_image(nan, Vec4::W);
+        group.nearest_image_transform(
+            nan,
+            GlobalTransform::from_translation(Vec3::new(0.1, 0.0, 0.0)),
+        );
+    }
+
+    #[test]
+    fn nearest_image_is_nearest() {
+        let group = QuotientGroup::lens_space(7, 3);
+        let from = Transform::from_translation(Vec3::new(0.3, 0.1, 0.0)) * Vec4::W;
+        let point = Transform::from_translation(Vec3::new(-1.0, 2.0, 0.5)) * Vec4::W;
+        let nearest = group.nearest_image(from, point);
+        for &g in group.elements() {
+            assert!(nearest.dot(from) >= (g * point).dot(from) - 1e-6);
+        }
+    }
+
+    #[test]
+    fn dodecahedral_cells() {
+        let group = QuotientGroup::poincare_dodecahedral();
+        // The fundamental domain has 12 faces, one for each nearest element.
+        let nearest = group.elements()[1..]
+            .iter()
+            .filter(|g| (g.left.w - (1. + 5f32.sqrt()) / 4.).abs() < 1e-4)
+            .count();
+        assert_eq!(nearest, 12);
+
+        let transform = Transform::from_translation(Vec3::new(0.1, -0.05, 0.2));
+        assert_eq!(group.cell(transform * Vec4::W), 0);
+        for (i, &g) in group.elements().iter().enumerate() {
+            let moved = Transform {
+                biquat: g * transform.biquat,
+            };
+            assert_eq!(group.cell(moved * Vec4::W), i);
+            assert!(group.wrap(moved).biquat.abs_diff_eq(transform.biquat, 1e-4));
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::{
+    query::Changed,
+    schedule::ParallelSystemDescriptorCoercion,
+    system::{IntoSystem, Query, Res, ResMut},
+};
+use bevy_transform::TransformSystem;
+
+use crate::components::Transform;
+
+/// Tracks how far [`Transform`]s drift from being isometries through rounding error.
+///
+/// Multiplying a [`Transform`] into itself every frame, as a camera controller does,
+/// slowly lets its quaternions drift away from unit length, and the transform away from SO(4).
+/// [`transform_renormalize_system`] normalizes every changed [`Transform`] whose
+/// [`Biquaternion::drift`](crate::biquaternion::Biquaternion::drift) exceeds `threshold`.
+/// Add the [`TransformDriftDiagnosticsPlugin`] to report the largest drift with the other diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformDrift {
+    /// The drift above which a changed [`Transform`] is normalized.
+    pub threshold: f32,
+    /// The largest drift seen so far, before normalization. Reset it to zero to start measuring again.
+    pub max_drift: f32,
+}
+
+impl Default for TransformDrift {
+    fn default() -> Self {
+        Self {
+            threshold: 1e-4,
+            max_drift: 0.,
+        }
+    }
+}
+
+/// Normalizes each changed [`Transform`] that has drifted further than
+/// [`TransformDrift::threshold`], and records the largest drift in [`TransformDrift::max_drift`].
+///
+/// This runs in [`CoreStage::PostUpdate`](bevy_app::CoreStage::PostUpdate),
+/// before the transforms are propagated.
+pub fn transform_renormalize_system(
+    mut drift: ResMut<TransformDrift>,
+    mut query: Query<&mut Transform, Changed<Transform>>,
+) {
+    let threshold = drift.threshold;
+    let mut max_drift = drift.max_drift;
+    for mut transform in query.iter_mut() {
+        let transform_drift = transform.biquat.drift();
+        max_drift = max_drift.max(transform_drift);
+        if transform_drift > threshold {
+            transform.biquat = transform.biquat.normalize();
+        }
+    }
+    // Only write when needed, so that the resource is not marked as changed every frame.
+    if max_drift > drift.max_drift {
+        drift.max_drift = max_drift;
+    }
+}
+
+/// Adds the "transform_drift" diagnostic, which measures [`TransformDrift::max_drift`]
+/// every frame, so that it is reported along with the frame time and the rest.
+/// Add it after `DiagnosticsPlugin` and [`TransformPlugin`](crate::TransformPlugin).
+#[derive(Default)]
+pub struct TransformDriftDiagnosticsPlugin;
+
+impl Plugin for TransformDriftDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_startup_system(Self::setup_system.system())
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                Self::diagnostic_system
+                    .system()
+                    .after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+impl TransformDriftDiagnosticsPlugin {
+    pub const TRANSFORM_DRIFT: DiagnosticId =
+        DiagnosticId::from_u128(4999118756899268165756244656600367658);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::TRANSFORM_DRIFT,
+            "transform_drift",
+            20,
+        ));
+    }
+
+    pub fn diagnostic_system(mut diagnostics: ResMut<Diagnostics>, drift: Res<TransformDrift>) {
+        diagnostics.add_measurement(Self::TRANSFORM_DRIFT, drift.max_drift as f64);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{
+        schedule::{Schedule, Stage, SystemStage},
+        world::World,
+    };
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::biquaternion::Biquaternion;
+
+    #[test]
+    fn renormalizes_drifted() {
+        let mut world = World::default();
+        world.insert_resource(TransformDrift::default());
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(transform_renormalize_system.system());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", update_stage);
+
+        let transform = Transform::from_translation(Vec3::new(0.3, 0.0, -0.2));
+        let slightly = world
+            .spawn()
+            .insert(Transform {
+                biquat: transform.biquat * 1.00001,
+            })
+            .id();
+        let drifted = world
+            .spawn()
+            .insert(Transform {
+                biquat: Biquaternion {
+                    left: transform.biquat.left * 1.01,
+                    right: transform.biquat.right,
+                },
+            })
+            .id();
+        schedule.run(&mut world);
+
+        let drift = *world.get_resource::<TransformDrift>().unwrap();
+        assert!((drift.max_drift - 0.01).abs() < 1e-5);
+        // Below the threshold, the transform is left alone.
+        assert_eq!(
+            world.get::<Transform>(slightly).unwrap().biquat,
+            transform.biquat * 1.00001
+        );
+        let renormalized = world.get::<Transform>(drifted).unwrap().biquat;
+        assert!(renormalized.drift() < 1e-6);
+        assert!(renormalized.abs_diff_eq(transform.biquat, 1e-6));
+    }
+
+    #[test]
+    fn drift_diagnostic() {
+        let mut world = World::default();
+        world.insert_resource(Diagnostics::default());
+        world.insert_resource(TransformDrift {
+            max_drift: 0.002,
+            ..Default::default()
+        });
+
+        let mut startup_stage = SystemStage::parallel();
+        startup_stage.add_system(TransformDriftDiagnosticsPlugin::setup_system.system());
+        startup_stage.run(&mut world);
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(TransformDriftDiagnosticsPlugin::diagnostic_system.system());
+        update_stage.run(&mut world);
+
+        let diagnostics = world.get_resource::<Diagnostics>().unwrap();
+        let diagnostic = diagnostics
+            .get(TransformDriftDiagnosticsPlugin::TRANSFORM_DRIFT)
+            .unwrap();
+        assert_eq!(diagnostic.name, "transform_drift");
+        assert!((diagnostic.value().unwrap() - 0.002).abs() < 1e-9);
+    }
+}
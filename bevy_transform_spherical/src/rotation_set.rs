@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use crate::biquaternion::Biquaternion;
+
+/// A hashable stand-in for a [`Biquaternion`]: the cell of a grid containing its
+/// [canonical form](Biquaternion::canonicalize). See [`Biquaternion::key`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BiquaternionKey(pub [i32; 8]);
+
+impl Biquaternion {
+    /// The cell containing the canonical form of `self`, in a grid of cubes of side `resolution`.
+    ///
+    /// `self` and `-self` have the same key. Rotations that are close to each other
+    /// usually share a key too, but not always, because a grid line can fall between them.
+    /// To find every rotation close to a given one, use a [`RotationSet`], which also looks
+    /// in the neighboring cells.
+    #[inline]
+    pub fn key(self, resolution: f32) -> BiquaternionKey {
+        let mut key = [0; 8];
+        for (k, c) in key.iter_mut().zip(components(self.canonicalize()).iter()) {
+            *k = (c / resolution).round() as i32;
+        }
+        BiquaternionKey(key)
+    }
+}
+
+/// A set of rotations, none of which is approximately the same as another:
+/// inserting a [`Biquaternion`] within `max_abs_diff` of one already there,
+/// or of its negation, does nothing. See [`Biquaternion::approx_same_rotation`].
+///
+/// Use it to deduplicate symmetry groups and positions.
+/// Lookups take constant time, as long as the rotations are more than
+/// `4 * max_abs_diff` apart.
+#[derive(Debug, Clone)]
+pub struct RotationSet {
+    max_abs_diff: f32,
+    elements: Vec<Biquaternion>,
+    cells: HashMap<BiquaternionKey, Vec<usize>>,
+}
+
+impl RotationSet {
+    /// Creates an empty set, treating rotations as the same if they are within `max_abs_diff`.
+    pub fn new(max_abs_diff: f32) -> Self {
+        Self {
+            max_abs_diff,
+            elements: Vec::new(),
+            cells: HashMap::new(),
+        }
+    }
+
+    /// The side of the grid cells, chosen so that each rotation close to a given one is
+    /// in the same cell, or across the nearest face, edge, or corner.
+    fn resolution(&self) -> f32 {
+        4. * self.max_abs_diff
+    }
+
+    /// The index in [`RotationSet::elements`] of the element that is approximately the
+    /// same rotation as `biquat`, if there is one.
+    pub fn position(&self, biquat: Biquaternion) -> Option<usize> {
+        let canonical = biquat.canonicalize();
+        // The canonical sign is ambiguous when a leading component is close to the threshold,
+        // so look for the negation as well.
+        [canonical, -canonical]
+            .iter()
+            .flat_map(|&candidate| self.nearby_keys(candidate))
+            .filter_map(|key| self.cells.get(&key))
+            .flatten()
+            .copied()
+            .find(|&i| self.elements[i].approx_same_rotation(biquat, self.max_abs_diff))
+    }
+
+    /// Returns true if the set has an element that is approximately the same rotation as `biquat`.
+    pub fn contains(&self, biquat: Biquaternion) -> bool {
+        self.position(biquat).is_some()
+    }
+
+    /// Adds `biquat` to the set, unless it is approximately the same rotation as an element.
+    /// Returns whether it was added.
+    pub fn insert(&mut self, biquat: Biquaternion) -> bool {
+        if self.contains(biquat) {
+            return false;
+        }
+        let key = biquat.key(self.resolution());
+        self.cells.entry(key).or_default().push(self.elements.len());
+        self.elements.push(biquat);
+        true
+    }
+
+    /// The elements of the set, in the order they were inserted.
+    pub fn elements(&self) -> &[Biquaternion] {
+        &self.elements
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// The key of `canonical`, and the keys across each face, edge and corner of its cell
+    /// that are within `max_abs_diff` of it.
+    fn nearby_keys(&self, canonical: Biquaternion) -> Vec<BiquaternionKey> {
+        let resolution = self.resolution();
+        let center = canonical.key(resolution);
+        let mut keys = vec![center];
+        for (i, c) in components(canonical).iter().enumerate() {
+            let offset = c / resolution - center.0[i] as f32;
+            // Cells are `4 * max_abs_diff` wide, so only values within a quarter of a cell
+            // of the boundary have neighbors across it.
+            let step = if offset > 0.25 {
+                1
+            } else if offset < -0.25 {
+                -1
+            } else {
+                continue;
+            };
+            for j in 0..keys.len() {
+                let mut neighbor = keys[j];
+                neighbor.0[i] += step;
+                keys.push(neighbor);
+            }
+        }
+        keys
+    }
+}
+
+impl Extend<Biquaternion> for RotationSet {
+    fn extend<I: IntoIterator<Item = Biquaternion>>(&mut self, iter: I) {
+        for biquat in iter {
+            self.insert(biquat);
+        }
+    }
+}
+
+impl From<RotationSet> for Vec<Biquaternion> {
+    fn from(set: RotationSet) -> Self {
+        set.elements
+    }
+}
+
+fn components(biquat: Biquaternion) -> [f32; 8] {
+    let Biquaternion { left, right } = biquat;
+    [
+        left.x, left.y, left.z, left.w, right.x, right.y, right.z, right.w,
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::components::Transform;
+    use bevy_math::{Quat, Vec3};
+
+    #[test]
+    fn negation_is_the_same_rotation() {
+        let biquat = (Transform::from_translation(Vec3::new(0.3, -1.2, 0.4))
+            * Transform::from_rotation(Quat::from_rotation_y(2.0)))
+        .biquat;
+        assert_ne!(biquat, -biquat);
+        assert!(biquat.approx_same_rotation(-biquat, 1e-6));
+        assert_eq!(biquat.canonicalize(), (-biquat).canonicalize());
+        assert_eq!(biquat.key(1e-3), (-biquat).key(1e-3));
+        assert!(!biquat.approx_same_rotation(Biquaternion::IDENTITY, 1e-3));
+
+        // A leading zero is skipped, even with rounding error of either sign.
+        let half_turn = Biquaternion::from_rotation(Quat::from_rotation_x(std::f32::consts::PI));
+        let noisy = |e: f32| Biquaternion {
+            left: half_turn.left + Quat::from_xyzw(0., 0., 0., e),
+            ..half_turn
+        };
+        assert_eq!(
+            noisy(1e-6).canonicalize().left.x,
+            noisy(-1e-6).canonicalize().left.x
+        );
+    }
+
+    #[test]
+    fn set_deduplicates_across_cells() {
+        let mut set = RotationSet::new(1e-4);
+        let biquat = Transform::from_rotation(Quat::from_rotation_z(0.7)).biquat;
+        assert!(set.insert(biquat));
+        // Nudge every component by less than the tolerance, in both directions,
+        // so that some of the nudged copies fall in neighboring cells.
+        for i in 0..64 {
+            let nudge = |bit: u32| if i & (1 << bit) == 0 { 9e-5 } else { -9e-5 };
+            let nudged = Biquaternion {
+                left: biquat.left + Quat::from_xyzw(nudge(0), nudge(1), nudge(2), 0.),
+                right: biquat.right + Quat::from_xyzw(nudge(3), 0., nudge(4), nudge(5)),
+            };
+            assert!(!set.insert(nudged));
+            assert!(!set.insert(-nudged));
+        }
+        assert_eq!(set.len(), 1);
+
+        assert!(set.insert(Biquaternion::IDENTITY));
+        assert_eq!(set.position(-Biquaternion::IDENTITY), Some(1));
+        assert!(set.insert(Biquaternion {
+            left: Quat::IDENTITY,
+            right: -Quat::IDENTITY,
+        }));
+        assert_eq!(set.len(), 3);
+    }
+}
@@ -0,0 +1,303 @@
+use bevy_math::{Mat4, Vec3, Vec4};
+
+use crate::{
+    components::{GlobalTransform, HyperbolicGlobalTransform, HyperbolicTransform, Transform},
+    geometry,
+    transform_propagate_system::PropagateTransform,
+};
+
+/// A space of constant curvature, together with the transform types that move things around in it.
+///
+/// Code written against this trait works unchanged in [`Spherical`], [`Elliptic`], [`Euclidean`] and [`Hyperbolic`] space.
+/// Propagate transforms with
+/// [`transform_propagate_system::<G::Transform>`](crate::transform_propagate_system::transform_propagate_system).
+pub trait Geometry: Send + Sync + 'static {
+    /// The local transform component.
+    type Transform: PropagateTransform<Global = Self::GlobalTransform>;
+    /// The global transform component.
+    type GlobalTransform: Copy + Send + Sync + 'static + From<Self::Transform>;
+    /// A point of the space.
+    type Point: Copy;
+    /// A tangent vector at some point of the space.
+    type Vector: Copy;
+
+    /// The point the identity transform leaves at the center of the view.
+    fn origin() -> Self::Point;
+
+    /// The transform moving [`Geometry::origin`] along `translation`.
+    fn translation(translation: Vec3) -> Self::Transform;
+
+    /// Where `transform` takes [`Geometry::origin`].
+    fn position(transform: &Self::GlobalTransform) -> Self::Point;
+
+    /// The matrix of `transform`, acting on [`Geometry::homogeneous`] coordinates.
+    fn compute_matrix(transform: &Self::GlobalTransform) -> Mat4;
+
+    /// The coordinates of `point` as uploaded to shaders.
+    fn homogeneous(point: Self::Point) -> Vec4;
+
+    /// The length of the shortest geodesic from `a` to `b`.
+    fn distance(a: Self::Point, b: Self::Point) -> f32;
+
+    /// Follows the geodesic leaving `point` with initial velocity `vector` for unit time.
+    fn exp(point: Self::Point, vector: Self::Vector) -> Self::Point;
+
+    /// The inverse of [`Geometry::exp`]: the velocity at `point` of the shortest geodesic reaching `target` in unit time.
+    fn log(point: Self::Point, target: Self::Point) -> Self::Vector;
+}
+
+/// The 3-sphere of curvature +1, with points on the unit sphere in `Vec4`.
+/// Uses [`Transform`] and [`GlobalTransform`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Spherical;
+
+impl Geometry for Spherical {
+    type Transform = Transform;
+    type GlobalTransform = GlobalTransform;
+    type Point = Vec4;
+    type Vector = Vec4;
+
+    fn origin() -> Vec4 {
+        Vec4::W
+    }
+
+    fn translation(translation: Vec3) -> Transform {
+        Transform::from_translation(translation)
+    }
+
+    fn position(transform: &GlobalTransform) -> Vec4 {
+        transform.position()
+    }
+
+    fn compute_matrix(transform: &GlobalTransform) -> Mat4 {
+        transform.compute_matrix()
+    }
+
+    fn homogeneous(point: Vec4) -> Vec4 {
+        point
+    }
+
+    fn distance(a: Vec4, b: Vec4) -> f32 {
+        geometry::distance(a, b)
+    }
+
+    fn exp(point: Vec4, vector: Vec4) -> Vec4 {
+        geometry::exp(point, vector)
+    }
+
+    fn log(point: Vec4, target: Vec4) -> Vec4 {
+        geometry::log(point, target)
+    }
+}
+
+/// Elliptic space RP³: the 3-sphere of curvature +1 with antipodal points identified.
+/// Uses [`Transform`] and [`GlobalTransform`], like [`Spherical`].
+///
+/// Points are unit `Vec4`s, and `p` and `-p` are the same point;
+/// [`Geometry::position`] returns the representative chosen by
+/// [`GlobalTransform::elliptic_position`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Elliptic;
+
+impl Geometry for Elliptic {
+    type Transform = Transform;
+    type GlobalTransform = GlobalTransform;
+    type Point = Vec4;
+    type Vector = Vec4;
+
+    fn origin() -> Vec4 {
+        Vec4::W
+    }
+
+    fn translation(translation: Vec3) -> Transform {
+        Transform::from_translation(translation)
+    }
+
+    fn position(transform: &GlobalTransform) -> Vec4 {
+        transform.elliptic_position()
+    }
+
+    fn compute_matrix(transform: &GlobalTransform) -> Mat4 {
+        transform.compute_matrix()
+    }
+
+    fn homogeneous(point: Vec4) -> Vec4 {
+        point
+    }
+
+    fn distance(a: Vec4, b: Vec4) -> f32 {
+        // The chordal distance to the nearer of the two representatives of `b`.
+        let chord = (a - b).length().min((a + b).length());
+        (chord * 0.5).asin() * 2.
+    }
+
+    fn exp(point: Vec4, vector: Vec4) -> Vec4 {
+        Spherical::exp(point, vector)
+    }
+
+    fn log(point: Vec4, target: Vec4) -> Vec4 {
+        let target = if point.dot(target) < 0. {
+            -target
+        } else {
+            target
+        };
+        Spherical::log(point, target)
+    }
+}
+
+/// Flat space, using Bevy's own [`Transform`](bevy_transform::components::Transform)
+/// and [`GlobalTransform`](bevy_transform::components::GlobalTransform).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Euclidean;
+
+impl Geometry for Euclidean {
+    type Transform = bevy_transform::components::Transform;
+    type GlobalTransform = bevy_transform::components::GlobalTransform;
+    type Point = Vec3;
+    type Vector = Vec3;
+
+    fn origin() -> Vec3 {
+        Vec3::ZERO
+    }
+
+    fn translation(translation: Vec3) -> Self::Transform {
+        Self::Transform::from_translation(translation)
+    }
+
+    fn position(transform: &Self::GlobalTransform) -> Vec3 {
+        transform.translation
+    }
+
+    fn compute_matrix(transform: &Self::GlobalTransform) -> Mat4 {
+        transform.compute_matrix()
+    }
+
+    fn homogeneous(point: Vec3) -> Vec4 {
+        point.extend(1.)
+    }
+
+    fn distance(a: Vec3, b: Vec3) -> f32 {
+        a.distance(b)
+    }
+
+    fn exp(point: Vec3, vector: Vec3) -> Vec3 {
+        point + vector
+    }
+
+    fn log(point: Vec3, target: Vec3) -> Vec3 {
+        target - point
+    }
+}
+
+/// Hyperbolic space of curvature -1, with points on the hyperboloid
+/// `x² + y² + z² - w² = -1, w > 0`.
+/// Uses [`HyperbolicTransform`] and [`HyperbolicGlobalTransform`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Hyperbolic;
+
+/// The Minkowski inner product, with signature `(+, +, +, -)`.
+#[inline]
+fn minkowski_dot(a: Vec4, b: Vec4) -> f32 {
+    a.x * b.x + a.y * b.y + a.z * b.z - a.w * b.w
+}
+
+impl Geometry for Hyperbolic {
+    type Transform = HyperbolicTransform;
+    type GlobalTransform = HyperbolicGlobalTransform;
+    type Point = Vec4;
+    type Vector = Vec4;
+
+    fn origin() -> Vec4 {
+        Vec4::W
+    }
+
+    fn translation(translation: Vec3) -> HyperbolicTransform {
+        HyperbolicTransform::from_translation(translation)
+    }
+
+    fn position(transform: &HyperbolicGlobalTransform) -> Vec4 {
+        transform.position()
+    }
+
+    fn compute_matrix(transform: &HyperbolicGlobalTransform) -> Mat4 {
+        transform.compute_matrix()
+    }
+
+    fn homogeneous(point: Vec4) -> Vec4 {
+        point
+    }
+
+    fn distance(a: Vec4, b: Vec4) -> f32 {
+        (-minkowski_dot(a, b)).max(1.).acosh()
+    }
+
+    fn exp(point: Vec4, vector: Vec4) -> Vec4 {
+        let length = minkowski_dot(vector, vector).max(0.).sqrt();
+        if length < 0.0001 {
+            point + vector
+        } else {
+            point * length.cosh() + vector * (length.sinh() / length)
+        }
+    }
+
+    fn log(point: Vec4, target: Vec4) -> Vec4 {
+        let tangent = target + point * minkowski_dot(point, target);
+        let sinh = minkowski_dot(tangent, tangent).max(0.).sqrt();
+        if sinh < 0.0001 {
+            tangent
+        } else {
+            tangent * (sinh.asinh() / sinh)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn position_after<G: Geometry>(translation: Vec3) -> G::Point {
+        G::position(&G::GlobalTransform::from(G::translation(translation)))
+    }
+
+    fn check_exp_log<G: Geometry>(target: Vec3, max_abs_diff: f32) {
+        let point = position_after::<G>(Vec3::new(0.2, -0.4, 0.1));
+        let target = position_after::<G>(target);
+        let round_trip = G::exp(point, G::log(point, target));
+        assert!(
+            G::homogeneous(round_trip).abs_diff_eq(G::homogeneous(target), max_abs_diff),
+            "{:?} != {:?}",
+            G::homogeneous(round_trip),
+            G::homogeneous(target)
+        );
+    }
+
+    #[test]
+    fn exp_inverts_log() {
+        check_exp_log::<Spherical>(Vec3::new(1.0, 0.5, -1.5), 1e-5);
+        check_exp_log::<Elliptic>(Vec3::new(1.0, 0.5, -1.5), 1e-5);
+        check_exp_log::<Euclidean>(Vec3::new(1.0, 0.5, -1.5), 1e-5);
+        check_exp_log::<Hyperbolic>(Vec3::new(1.0, 0.5, -1.5), 1e-4);
+    }
+
+    #[test]
+    fn translation_moves_by_distance() {
+        let translation = Vec3::new(0.6, -0.3, 0.9);
+        let spherical = position_after::<Spherical>(translation);
+        let hyperbolic = position_after::<Hyperbolic>(translation);
+        assert!((Spherical::distance(Vec4::W, spherical) - translation.length()).abs() < 1e-5);
+        assert!((Hyperbolic::distance(Vec4::W, hyperbolic) - translation.length()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn elliptic_identifies_antipodes() {
+        let far = Transform::from_translation(Vec3::new(0.0, 0.0, 2.5));
+        let position = Elliptic::position(&far.into());
+        assert!(position.w >= 0.);
+        assert!((Elliptic::distance(Vec4::W, position) - (PI - 2.5)).abs() < 1e-5);
+        assert!((Elliptic::distance(Vec4::W, -position) - (PI - 2.5)).abs() < 1e-5);
+        assert!(
+            Elliptic::exp(Vec4::W, Elliptic::log(Vec4::W, -position)).abs_diff_eq(position, 1e-5)
+        );
+    }
+}
@@ -0,0 +1,604 @@
+use bevy_app::prelude::*;
+use bevy_core::Time;
+use bevy_ecs::{
+    schedule::ParallelSystemDescriptorCoercion,
+    system::{IntoSystem, Query, Res},
+};
+use bevy_math::{Mat4, Vec4};
+use bevy_transform::TransformSystem;
+
+use crate::{
+    components::Transform,
+    geometry::{self, any_orthogonal},
+};
+
+/// The number of samples per segment in a [`Spline`]'s arc length table.
+const SAMPLES_PER_SEGMENT: usize = 32;
+
+/// A smooth curve on the unit 3-sphere, made of cubic Bézier segments.
+///
+/// Each segment is evaluated by the geodesic de Casteljau algorithm: the usual construction
+/// of a Bézier curve by repeated linear interpolation, with every line segment replaced by
+/// the shortest geodesic, using [`geometry::geodesic_point`].
+/// So a segment whose control points lie on one great circle stays on it,
+/// and a spline may go all the way around the universe.
+///
+/// The curve is parametrized both by `t`, from 0 at the start to the number of segments at the end,
+/// and by arc length, using a table built when the spline is created.
+/// Use the `_at_distance` methods to move along it at constant speed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spline {
+    segments: Vec<[Vec4; 4]>,
+    /// The arc length from the start to each sample, `SAMPLES_PER_SEGMENT` per segment.
+    lengths: Vec<f32>,
+    closed: bool,
+}
+
+impl Spline {
+    /// Creates a spline from the control points of consecutive cubic Bézier segments,
+    /// sharing their endpoints: `3n + 1` points for `n` segments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of points is not `3n + 1` for some `n > 0`.
+    pub fn bezier(points: &[Vec4]) -> Self {
+        assert!(
+            points.len() >= 4 && points.len() % 3 == 1,
+            "a Bézier spline needs 3n + 1 control points"
+        );
+        let segments = points
+            .windows(4)
+            .step_by(3)
+            .map(|window| [window[0], window[1], window[2], window[3]])
+            .collect();
+        let closed = points[0].abs_diff_eq(points[points.len() - 1], 1e-6);
+        Self::from_segments(segments, closed)
+    }
+
+    /// Creates the Catmull-Rom spline passing through each of `points` in order.
+    ///
+    /// The velocity at each point is half the geodesic from the point before it to the point
+    /// after it, measured by [`geometry::log`]. If `closed`, the spline returns to the first point,
+    /// and is smooth there too; otherwise it starts and ends heading straight for its neighbors.
+    ///
+    /// Consecutive points must not be antipodal, since the shortest geodesic between them is
+    /// then undefined. To go all the way around the universe, add points in between.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than two points.
+    pub fn catmull_rom(points: &[Vec4], closed: bool) -> Self {
+        assert!(points.len() >= 2, "a spline needs at least two points");
+        let n = points.len();
+        let neighbor = |i: usize, offset: isize| -> Option<Vec4> {
+            let j = i as isize + offset;
+            if closed {
+                Some(points[j.rem_euclid(n as isize) as usize])
+            } else if j >= 0 && (j as usize) < n {
+                Some(points[j as usize])
+            } else {
+                None
+            }
+        };
+        let velocity = |i: usize| {
+            let p = points[i];
+            match (neighbor(i, -1), neighbor(i, 1)) {
+                (Some(before), Some(after)) => {
+                    (geometry::log(p, after) - geometry::log(p, before)) * 0.5
+                }
+                (None, Some(after)) => geometry::log(p, after),
+                (Some(before), None) => -geometry::log(p, before),
+                (None, None) => Vec4::ZERO,
+            }
+        };
+
+        let count = if closed { n } else { n - 1 };
+        let segments = (0..count)
+            .map(|i| {
+                let j = (i + 1) % n;
+                let (start, end) = (points[i], points[j]);
+                [
+                    start,
+                    geometry::exp(start, velocity(i) / 3.),
+                    geometry::exp(end, -velocity(j) / 3.),
+                    end,
+                ]
+            })
+            .collect();
+        Self::from_segments(segments, closed)
+    }
+
+    fn from_segments(segments: Vec<[Vec4; 4]>, closed: bool) -> Self {
+        let mut lengths = Vec::with_capacity(segments.len() * SAMPLES_PER_SEGMENT + 1);
+        lengths.push(0.);
+        let mut previous = segments[0][0];
+        for segment in &segments {
+            for k in 1..=SAMPLES_PER_SEGMENT {
+                let point = de_casteljau(segment, k as f32 / SAMPLES_PER_SEGMENT as f32);
+                let length = lengths[lengths.len() - 1] + geometry::distance(previous, point);
+                lengths.push(length);
+                previous = point;
+            }
+        }
+        Self {
+            segments,
+            lengths,
+            closed,
+        }
+    }
+
+    /// The control points of each cubic Bézier segment.
+    pub fn segments(&self) -> &[[Vec4; 4]] {
+        &self.segments
+    }
+
+    /// Whether the spline ends where it starts.
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// The largest value of `t`, which is the number of segments.
+    pub fn max_t(&self) -> f32 {
+        self.segments.len() as f32
+    }
+
+    /// The arc length of the whole spline, on the unit sphere.
+    pub fn length(&self) -> f32 {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    /// The point at parameter `t`, which is clamped to `0..=max_t`.
+    /// Segment `i` runs from `t = i` to `t = i + 1`.
+    pub fn position(&self, t: f32) -> Vec4 {
+        let (segment, s) = self.segment_at(t);
+        de_casteljau(&self.segments[segment], s)
+    }
+
+    /// The velocity at parameter `t`, with respect to `t`. It is tangent to the sphere at
+    /// [`Spline::position`].
+    ///
+    /// This is the exact derivative of the geodesic de Casteljau construction,
+    /// carried through each step alongside the point, so it is as accurate as the position.
+    /// At the start of a segment, it is the velocity leaving that segment's first point,
+    /// `3 * log(p0, p1)`.
+    pub fn velocity(&self, t: f32) -> Vec4 {
+        let (segment, s) = self.segment_at(t);
+        let (point, velocity) = de_casteljau_with_derivative(&self.segments[segment], s);
+        velocity - point * point.dot(velocity)
+    }
+
+    /// The parameter `t` at which the arc length from the start is `distance`.
+    /// On a closed spline, `distance` wraps around; otherwise it is clamped.
+    /// A NaN distance, or an infinite one on a closed spline, gives the start.
+    pub fn t_at_distance(&self, distance: f32) -> f32 {
+        let length = self.length();
+        let distance = if self.closed && length > 0. && distance.is_finite() {
+            distance.rem_euclid(length)
+        } else if distance.is_nan() || self.closed {
+            0.
+        } else {
+            distance.max(0.).min(length)
+        };
+        // The first sample at least `distance` along.
+        let k = match self.lengths.binary_search_by(|l| l.total_cmp(&distance)) {
+            Ok(k) => return k as f32 / SAMPLES_PER_SEGMENT as f32,
+            Err(k) => k.max(1).min(self.lengths.len() - 1),
+        };
+        let (before, after) = (self.lengths[k - 1], self.lengths[k]);
+        let fraction = if after > before {
+            (distance - before) / (after - before)
+        } else {
+            0.
+        };
+        (k as f32 - 1. + fraction) / SAMPLES_PER_SEGMENT as f32
+    }
+
+    /// The point at arc length `distance` from the start. See [`Spline::t_at_distance`].
+    pub fn position_at_distance(&self, distance: f32) -> Vec4 {
+        self.position(self.t_at_distance(distance))
+    }
+
+    /// A moving frame along the spline at parameter `t`.
+    ///
+    /// Like the Frenet frame, the transform is at [`Spline::position`],
+    /// with its local `-z` axis along the [`Spline::velocity`].
+    /// Its local `y` axis is the part of `up` orthogonal to that. If there is no such part,
+    /// the curve's normal, toward which it is bending, is used instead; this is the Frenet normal.
+    /// A fixed `up` keeps a camera level where the Frenet frame would flip over.
+    ///
+    /// The normal is estimated from the change in [`Spline::velocity`] across `t ± 0.01`,
+    /// which is only used for its direction, and only when `up` cannot be.
+    /// Where the curve is nearly straight, the normal is undefined, and an axis is used.
+    pub fn frame(&self, t: f32, up: Vec4) -> Transform {
+        let position = self.position(t);
+        let velocity = self.velocity(t);
+        let forward = if velocity.length_squared() > 1e-12 {
+            velocity.normalize()
+        } else {
+            any_orthogonal(position)
+        };
+        let orthogonal = |v: Vec4| v - position * position.dot(v) - forward * forward.dot(v);
+        let h = 1e-2;
+        let normal = self.velocity((t + h).min(self.max_t())) - self.velocity((t - h).max(0.));
+        // Two of the axes are always far enough from the plane of `position` and `forward`.
+        let up = [up, normal, Vec4::X, Vec4::Y, Vec4::Z, Vec4::W]
+            .iter()
+            .map(|&v| orthogonal(v))
+            .find(|v| v.length_squared() > 1e-8)
+            .unwrap()
+            .normalize();
+        let back = -forward;
+        let mut right = cross(up, back, position);
+        if Mat4::from_cols(right, up, back, position).determinant() < 0. {
+            right = -right;
+        }
+        Transform::from_matrix(Mat4::from_cols(right, up, back, position))
+    }
+
+    /// The moving frame at arc length `distance` from the start. See [`Spline::frame`].
+    pub fn frame_at_distance(&self, distance: f32, up: Vec4) -> Transform {
+        self.frame(self.t_at_distance(distance), up)
+    }
+
+    /// The segment containing `t`, and how far along it `t` is.
+    fn segment_at(&self, t: f32) -> (usize, f32) {
+        let t = t.max(0.).min(self.max_t());
+        let segment = (t.floor() as usize).min(self.segments.len() - 1);
+        (segment, t - segment as f32)
+    }
+}
+
+/// Moves the entity's [`Transform`] along a [`Spline`] at constant speed,
+/// using [`Spline::frame_at_distance`].
+///
+/// The spline is in the coordinates of the entity's parent, like [`Transform`] itself.
+#[derive(Debug, Clone)]
+pub struct SplineFollower {
+    pub spline: Spline,
+    /// How far along the spline the entity is, on the unit sphere.
+    pub distance: f32,
+    /// How fast the entity moves along the spline, in radians per second.
+    /// Multiply by the [`UniverseRadius`](crate::universe::UniverseRadius) to get world units.
+    pub speed: f32,
+    /// The direction the entity's local `y` axis leans toward. See [`Spline::frame`].
+    pub up: Vec4,
+}
+
+impl SplineFollower {
+    pub fn new(spline: Spline, speed: f32) -> Self {
+        Self {
+            spline,
+            distance: 0.,
+            speed,
+            up: Vec4::Y,
+        }
+    }
+
+    /// Moves `seconds` further along the spline, and returns the new [`Transform`].
+    /// On a closed spline, this goes around forever; otherwise it stops at the end.
+    pub fn advance(&mut self, seconds: f32) -> Transform {
+        self.distance = self.distance_after(seconds);
+        self.spline.frame_at_distance(self.distance, self.up)
+    }
+
+    /// The [`SplineFollower::distance`] after moving `seconds` further along the spline.
+    fn distance_after(&self, seconds: f32) -> f32 {
+        let distance = self.distance + self.speed * seconds;
+        let length = self.spline.length();
+        if self.spline.is_closed() && length > 0. {
+            distance.rem_euclid(length)
+        } else {
+            distance.max(0.).min(length)
+        }
+    }
+}
+
+/// Advances each [`SplineFollower`] and writes its [`Transform`].
+///
+/// The [`Transform`] is only written when the follower moves or is changed,
+/// so a stopped follower, or one at the end of an open spline, does not mark it as changed.
+pub fn spline_follower_system(
+    time: Res<Time>,
+    mut query: Query<(&mut SplineFollower, &mut Transform)>,
+) {
+    for (mut follower, mut transform) in query.iter_mut() {
+        let distance = follower.distance_after(time.delta_seconds());
+        if distance != follower.distance || follower.is_changed() {
+            follower.distance = distance;
+            *transform = follower.spline.frame_at_distance(distance, follower.up);
+        }
+    }
+}
+
+/// Adds the [`spline_follower_system`].
+/// Add it after `CorePlugin`, which provides [`Time`], and [`TransformPlugin`](crate::TransformPlugin).
+#[derive(Default)]
+pub struct SplinePlugin;
+
+impl Plugin for SplinePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_system_to_stage(
+            CoreStage::PostUpdate,
+            spline_follower_system
+                .system()
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Evaluates a cubic Bézier segment at `s` by geodesic de Casteljau.
+#[inline]
+fn de_casteljau(points: &[Vec4; 4], s: f32) -> Vec4 {
+    let a = geometry::geodesic_point(points[0], points[1], s);
+    let b = geometry::geodesic_point(points[1], points[2], s);
+    let c = geometry::geodesic_point(points[2], points[3], s);
+    let d = geometry::geodesic_point(a, b, s);
+    let e = geometry::geodesic_point(b, c, s);
+    geometry::geodesic_point(d, e, s)
+}
+
+/// Evaluates a cubic Bézier segment at `s` by geodesic de Casteljau,
+/// along with its derivative with respect to `s`.
+#[inline]
+fn de_casteljau_with_derivative(points: &[Vec4; 4], s: f32) -> (Vec4, Vec4) {
+    let step = |a: (Vec4, Vec4), b: (Vec4, Vec4)| geodesic_point_with_derivative(a, b, s);
+    let [p0, p1, p2, p3] = points.map(|p| (p, Vec4::ZERO));
+    let a = step(p0, p1);
+    let b = step(p1, p2);
+    let c = step(p2, p3);
+    let d = step(a, b);
+    let e = step(b, c);
+    step(d, e)
+}
+
+/// [`geometry::geodesic_point`] from `a.0` to `b.0` at `s`, and its derivative with respect to `s`,
+/// given the derivatives `a.1` and `b.1` of the endpoints.
+#[inline]
+fn geodesic_point_with_derivative(a: (Vec4, Vec4), b: (Vec4, Vec4), s: f32) -> (Vec4, Vec4) {
+    let ((a, da), (b, db)) = (a, b);
+    let angle = geometry::distance(a, b);
+    let sin = angle.sin();
+    if angle < 0.0001 || sin < 0.0001 {
+        // Differentiate the normalized chord, as `geodesic_point` does for nearby points.
+        // Antipodal endpoints only meet at a cusp, where any direction will do.
+        let chord = a.lerp(b, s);
+        let point = chord.normalize();
+        let d_chord = da.lerp(db, s) + (b - a);
+        let derivative = (d_chord - point * point.dot(d_chord)) / chord.length();
+        return (geometry::geodesic_point(a, b, s), derivative);
+    }
+
+    // `cos(angle) = a · b`, so its derivative is `-sin(angle) * d_angle`.
+    let d_angle = -(da.dot(b) + a.dot(db)) / sin;
+    let (before, after) = ((1. - s) * angle, s * angle);
+    let point = (a * before.sin() + b * after.sin()) / sin;
+    let derivative = (a * (before.cos() * ((1. - s) * d_angle - angle))
+        + da * before.sin()
+        + b * (after.cos() * (s * d_angle + angle))
+        + db * after.sin()
+        - point * (angle.cos() * d_angle))
+        / sin;
+    (point, derivative)
+}
+
+/// A vector orthogonal to `a`, `b` and `c`: the 4-dimensional cross product.
+#[inline]
+fn cross(a: Vec4, b: Vec4, c: Vec4) -> Vec4 {
+    let det3 = |a: [f32; 3], b: [f32; 3], c: [f32; 3]| {
+        a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+            + a[2] * (b[0] * c[1] - b[1] * c[0])
+    };
+    let without = |v: Vec4, i: usize| {
+        let v = [v.x, v.y, v.z, v.w];
+        let mut out = [0.; 3];
+        let mut k = 0;
+        for (j, &c) in v.iter().enumerate() {
+            if j != i {
+                out[k] = c;
+                k += 1;
+            }
+        }
+        out
+    };
+    let minor = |i: usize| det3(without(a, i), without(b, i), without(c, i));
+    Vec4::new(-minor(0), minor(1), -minor(2), minor(3))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        world::World,
+    };
+    use bevy_math::Vec3;
+    use std::{f32::consts::TAU, thread, time::Duration};
+
+    fn point(translation: Vec3) -> Vec4 {
+        Transform::from_translation(translation) * Vec4::W
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_points() {
+        let points = [
+            point(Vec3::ZERO),
+            point(Vec3::new(0.5, 0.1, 0.0)),
+            point(Vec3::new(0.8, 0.7, -0.3)),
+            point(Vec3::new(0.2, 1.2, 0.4)),
+        ];
+        for &closed in &[false, true] {
+            let spline = Spline::catmull_rom(&points, closed);
+            for (i, &p) in points.iter().enumerate() {
+                assert!(spline.position(i as f32).abs_diff_eq(p, 1e-5));
+            }
+            for k in 0..=30 {
+                let t = spline.max_t() * k as f32 / 30.;
+                assert!((spline.position(t).length() - 1.).abs() < 1e-5);
+                assert!(spline.velocity(t).dot(spline.position(t)).abs() < 1e-4);
+            }
+            // Smooth across inner points.
+            let h = 1e-2;
+            assert!(spline
+                .velocity(1. - h)
+                .abs_diff_eq(spline.velocity(1. + h), 0.1));
+        }
+        assert!(Spline::catmull_rom(&points, true)
+            .position(4.)
+            .abs_diff_eq(points[0], 1e-5));
+    }
+
+    #[test]
+    fn velocity_is_exact() {
+        let controls = [
+            point(Vec3::ZERO),
+            point(Vec3::new(0.3, 0.0, 0.1)),
+            point(Vec3::new(0.6, 0.8, 0.0)),
+            point(Vec3::new(0.2, 1.4, -0.5)),
+        ];
+        let spline = Spline::bezier(&controls);
+
+        // At the ends, a Bézier curve leaves toward its neighboring control point.
+        let start = geometry::log(controls[0], controls[1]) * 3.;
+        assert!(spline.velocity(0.).abs_diff_eq(start, 1e-4));
+        let end = geometry::log(controls[3], controls[2]) * -3.;
+        assert!(spline.velocity(1.).abs_diff_eq(end, 1e-4));
+
+        // In between, it matches the change in position.
+        for k in 1..10 {
+            let t = k as f32 / 10.;
+            let h = 1e-3;
+            let difference = (spline.position(t + h) - spline.position(t - h)) / (2. * h);
+            assert!(spline.velocity(t).abs_diff_eq(difference, 1e-2));
+        }
+    }
+
+    #[test]
+    fn around_the_universe() {
+        // Points around a great circle give back the great circle, at constant speed.
+        let points: Vec<_> = (0..6)
+            .map(|i| point(Vec3::Z * TAU * i as f32 / 6.))
+            .collect();
+        let spline = Spline::catmull_rom(&points, true);
+        assert!((spline.length() - TAU).abs() < 1e-3);
+        for k in 0..12 {
+            let distance = TAU * k as f32 / 12.;
+            let p = spline.position_at_distance(distance);
+            assert!(p.abs_diff_eq(point(Vec3::Z * distance), 1e-3));
+        }
+        assert!(spline
+            .position_at_distance(TAU + 0.5)
+            .abs_diff_eq(spline.position_at_distance(0.5), 1e-4));
+    }
+
+    #[test]
+    fn arc_length_is_uniform() {
+        let controls = [
+            point(Vec3::ZERO),
+            point(Vec3::new(0.05, 0.0, 0.0)),
+            point(Vec3::new(0.1, 0.0, 0.0)),
+            point(Vec3::new(1.5, 1.0, 0.0)),
+        ];
+        let spline = Spline::bezier(&controls);
+        let steps = 20;
+        let step = spline.length() / steps as f32;
+        for k in 0..steps {
+            let a = spline.position_at_distance(step * k as f32);
+            let b = spline.position_at_distance(step * (k + 1) as f32);
+            assert!((geometry::distance(a, b) - step).abs() < step * 0.02);
+        }
+    }
+
+    #[test]
+    fn non_finite_distance() {
+        let points = [point(Vec3::ZERO), point(Vec3::X), point(Vec3::Y)];
+        for &closed in &[false, true] {
+            let spline = Spline::catmull_rom(&points, closed);
+            assert_eq!(spline.t_at_distance(f32::NAN), 0.);
+            assert_eq!(spline.t_at_distance(f32::NEG_INFINITY), 0.);
+        }
+        let open = Spline::catmull_rom(&points, false);
+        assert_eq!(open.t_at_distance(f32::INFINITY), open.max_t());
+        let closed = Spline::catmull_rom(&points, true);
+        assert_eq!(closed.t_at_distance(f32::INFINITY), 0.);
+    }
+
+    #[test]
+    fn frame_follows_curve() {
+        let points = [
+            point(Vec3::ZERO),
+            point(Vec3::new(0.0, 0.0, -0.5)),
+            point(Vec3::new(0.4, 0.0, -0.9)),
+        ];
+        let spline = Spline::catmull_rom(&points, false);
+        for &t in &[0.0, 0.5, 1.3, 2.0] {
+            let frame = spline.frame(t, Vec4::Y);
+            assert!(frame.biquat.is_normalized());
+            assert!((frame * Vec4::W).abs_diff_eq(spline.position(t), 1e-4));
+            let forward = spline.velocity(t).normalize();
+            assert!((frame * -Vec4::Z).abs_diff_eq(forward, 1e-3));
+            // The curve stays in the `xz` plane, so `y` is kept up.
+            assert!((frame * Vec4::Y).abs_diff_eq(Vec4::Y, 1e-3));
+        }
+
+        // With `up` along the curve, the frame bends toward the normal instead.
+        let frame = spline.frame(1.0, spline.velocity(1.0));
+        assert!(frame.biquat.is_finite());
+        assert!((frame * Vec4::Y).x > 0.);
+    }
+
+    #[test]
+    fn follower_stops_or_loops() {
+        let open = Spline::catmull_rom(&[point(Vec3::ZERO), point(Vec3::X)], false);
+        let mut follower = SplineFollower::new(open, 0.25);
+        let transform = follower.advance(2.0);
+        assert!((transform * Vec4::W).abs_diff_eq(point(Vec3::X * 0.5), 1e-3));
+        let transform = follower.advance(10.0);
+        assert!((transform * Vec4::W).abs_diff_eq(point(Vec3::X), 1e-4));
+
+        let points: Vec<_> = (0..4)
+            .map(|i| point(Vec3::Y * TAU * i as f32 / 4.))
+            .collect();
+        let mut follower = SplineFollower::new(Spline::catmull_rom(&points, true), 1.0);
+        follower.advance(TAU + 1.0);
+        assert!((follower.distance - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn follower_system_writes_on_move() {
+        let mut world = World::default();
+        world.insert_resource(Time::default());
+        let spline = Spline::catmull_rom(&[point(Vec3::ZERO), point(Vec3::X)], false);
+        let entity = world
+            .spawn()
+            .insert_bundle((
+                SplineFollower::new(spline, 1.0),
+                Transform::from_translation(Vec3::Z),
+            ))
+            .id();
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(spline_follower_system.system());
+
+        // A new follower places its entity, even without moving.
+        stage.run(&mut world);
+        let start = *world.get::<Transform>(entity).unwrap();
+        assert!((start * Vec4::W).abs_diff_eq(point(Vec3::ZERO), 1e-4));
+
+        // With no time passing, the transform is left alone.
+        let moved_by_hand = Transform::from_translation(Vec3::Y);
+        *world.get_mut::<Transform>(entity).unwrap() = moved_by_hand;
+        stage.run(&mut world);
+        assert_eq!(*world.get::<Transform>(entity).unwrap(), moved_by_hand);
+
+        // Once time passes, the follower moves on.
+        let mut time = world.get_resource_mut::<Time>().unwrap();
+        time.update();
+        thread::sleep(Duration::from_millis(5));
+        time.update();
+        stage.run(&mut world);
+        let distance = world.get::<SplineFollower>(entity).unwrap().distance;
+        assert!(distance > 0.);
+        let transform = *world.get::<Transform>(entity).unwrap();
+        assert!((transform * Vec4::W).abs_diff_eq(point(Vec3::X * distance), 1e-3));
+    }
+}
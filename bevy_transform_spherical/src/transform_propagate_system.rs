@@ -1,57 +1,204 @@
-use crate::components::{Children, GlobalTransform, Parent, Transform};
+use crate::components::{
+    Children, DGlobalTransform, DTransform, GlobalTransform, HyperbolicGlobalTransform,
+    HyperbolicTransform, LocalScale, Parent, Transform,
+};
 use bevy_ecs::{
+    component::Component,
     entity::Entity,
-    query::{Changed, With, Without},
-    system::Query,
+    query::{Changed, Or, With, Without},
+    system::{Query, RemovedComponents, Res},
 };
+use bevy_tasks::ComputeTaskPool;
+use std::{collections::HashSet, ops::Mul};
+
+/// A local transform component, which [`transform_propagate_system`] propagates
+/// down the entity hierarchy into its global counterpart.
+pub trait PropagateTransform: Component + Copy {
+    /// The global counterpart of this transform, such as [`GlobalTransform`] for [`Transform`].
+    type Global: Component + Copy + From<Self> + Mul<Self, Output = Self::Global>;
+
+    /// How far this transform has drifted from being an isometry through rounding error,
+    /// which [`transform_propagate_system`] debug-asserts is below [`MAX_PROPAGATED_DRIFT`].
+    ///
+    /// The default returns 0, which skips the check; [`DTransform`] and [`HyperbolicTransform`]
+    /// use it. [`Transform`] returns [`Biquaternion::drift`](crate::biquaternion::Biquaternion::drift).
+    #[inline]
+    fn drift(&self) -> f32 {
+        0.
+    }
+
+    /// Multiplies the scale of `global` by an entity's [`LocalScale`].
+    /// Transforms without a scale ignore it.
+    #[inline]
+    fn apply_scale(global: Self::Global, _scale: LocalScale) -> Self::Global {
+        global
+    }
+}
+
+/// The largest [`PropagateTransform::drift`] that [`transform_propagate_system`] accepts
+/// in debug builds. [`transform_renormalize_system`](crate::renormalize::transform_renormalize_system)
+/// keeps [`Transform`]s far below this.
+pub const MAX_PROPAGATED_DRIFT: f32 = 1e-2;
+
+impl PropagateTransform for Transform {
+    type Global = GlobalTransform;
+
+    #[inline]
+    fn drift(&self) -> f32 {
+        self.biquat.drift()
+    }
+
+    #[inline]
+    fn apply_scale(global: GlobalTransform, scale: LocalScale) -> GlobalTransform {
+        GlobalTransform {
+            scale: global.scale * scale.0,
+            ..global
+        }
+    }
+}
+
+impl PropagateTransform for DTransform {
+    type Global = DGlobalTransform;
+
+    #[inline]
+    fn apply_scale(global: DGlobalTransform, scale: LocalScale) -> DGlobalTransform {
+        DGlobalTransform {
+            scale: global.scale * scale.0 as f64,
+            ..global
+        }
+    }
+}
 
-/// Update [`GlobalTransform`] component of entities based on entity hierarchy and
-/// [`Transform`] component.
-pub fn transform_propagate_system(
+impl PropagateTransform for HyperbolicTransform {
+    type Global = HyperbolicGlobalTransform;
+}
+
+impl PropagateTransform for bevy_transform::components::Transform {
+    type Global = bevy_transform::components::GlobalTransform;
+}
+
+/// The number of roots propagated together on one thread.
+const ROOTS_PER_BATCH: usize = 32;
+
+/// Update the global transform component of entities (such as [`GlobalTransform`])
+/// based on entity hierarchy and their local transform component (such as [`Transform`]).
+///
+/// Each entity's [`LocalScale`], if any, is multiplied into the scale it inherits from its parent.
+///
+/// Only entities whose transform or scale changed, or whose scale was removed,
+/// and their descendants, are written.
+/// Each ancestor of a changed entity is marked first, so that subtrees with no changes
+/// are skipped without being visited, and the roots are then propagated in parallel
+/// on the [`ComputeTaskPool`].
+#[allow(clippy::type_complexity)]
+pub fn transform_propagate_system<T: PropagateTransform>(
+    task_pool: Res<ComputeTaskPool>,
     mut root_query: Query<
-        (Entity, Option<&Children>, &Transform, &mut GlobalTransform),
+        (
+            Entity,
+            Option<&Children>,
+            &T,
+            Option<&LocalScale>,
+            &mut T::Global,
+        ),
         Without<Parent>,
     >,
-    mut transform_query: Query<(&Transform, &mut GlobalTransform), With<Parent>>,
-    changed_transform_query: Query<Entity, Changed<Transform>>,
-    children_query: Query<Option<&Children>, (With<Parent>, With<GlobalTransform>)>,
+    transform_query: Query<(&T, Option<&LocalScale>, &mut T::Global), With<Parent>>,
+    changed_transform_query: Query<Entity, Or<(Changed<T>, Changed<LocalScale>)>>,
+    parent_query: Query<&Parent>,
+    children_query: Query<Option<&Children>, (With<Parent>, With<T::Global>)>,
+    removed_scale: RemovedComponents<LocalScale>,
 ) {
-    for (entity, children, transform, mut global_transform) in root_query.iter_mut() {
-        let mut changed = false;
-        if changed_transform_query.get(entity).is_ok() {
-            *global_transform = GlobalTransform::from(*transform);
-            changed = true;
-        }
-
-        if let Some(children) = children {
-            for child in children.iter() {
-                propagate_recursive(
-                    &global_transform,
-                    &changed_transform_query,
-                    &mut transform_query,
-                    &children_query,
-                    *child,
-                    changed,
-                );
+    let rescaled: HashSet<Entity> = removed_scale.iter().collect();
+    let mut dirty = HashSet::new();
+    for entity in changed_transform_query
+        .iter()
+        .chain(rescaled.iter().copied())
+    {
+        let mut entity = entity;
+        while let Ok(parent) = parent_query.get(entity) {
+            if !dirty.insert(parent.0) {
+                // The rest of the ancestors are already marked.
+                break;
             }
+            entity = parent.0;
         }
     }
+    if dirty.is_empty() && rescaled.is_empty() && changed_transform_query.iter().next().is_none() {
+        return;
+    }
+
+    root_query.par_for_each_mut(
+        &task_pool,
+        ROOTS_PER_BATCH,
+        |(entity, children, transform, scale, mut global_transform)| {
+            let changed = changed_transform_query.get(entity).is_ok() || rescaled.contains(&entity);
+            if changed {
+                debug_assert_drift(transform);
+                *global_transform = with_scale::<T>(T::Global::from(*transform), scale);
+            } else if !dirty.contains(&entity) {
+                return;
+            }
+
+            if let Some(children) = children {
+                for child in children.iter() {
+                    // SAFE: each root is visited by only one task.
+                    unsafe {
+                        propagate_recursive(
+                            &*global_transform,
+                            entity,
+                            &transform_query,
+                            &changed_transform_query,
+                            &parent_query,
+                            &children_query,
+                            &dirty,
+                            &rescaled,
+                            *child,
+                            changed,
+                        );
+                    }
+                }
+            }
+        },
+    );
 }
 
-fn propagate_recursive(
-    parent: &GlobalTransform,
-    changed_transform_query: &Query<Entity, Changed<Transform>>,
-    transform_query: &mut Query<(&Transform, &mut GlobalTransform), With<Parent>>,
-    children_query: &Query<Option<&Children>, (With<Parent>, With<GlobalTransform>)>,
+/// Propagates `parent`, the global transform of `parent_entity`, to `entity` and its descendants.
+///
+/// # Safety
+///
+/// No other call may be visiting `parent_entity` at the same time.
+/// Each entity is only visited from the entity named by its [`Parent`],
+/// so this ensures that no two calls write the same global transform,
+/// even if some [`Children`] list does not match the [`Parent`]s.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+unsafe fn propagate_recursive<T: PropagateTransform>(
+    parent: &T::Global,
+    parent_entity: Entity,
+    transform_query: &Query<(&T, Option<&LocalScale>, &mut T::Global), With<Parent>>,
+    changed_transform_query: &Query<Entity, Or<(Changed<T>, Changed<LocalScale>)>>,
+    parent_query: &Query<&Parent>,
+    children_query: &Query<Option<&Children>, (With<Parent>, With<T::Global>)>,
+    dirty: &HashSet<Entity>,
+    rescaled: &HashSet<Entity>,
     entity: Entity,
     mut changed: bool,
 ) {
-    changed |= changed_transform_query.get(entity).is_ok();
+    match parent_query.get(entity) {
+        Ok(actual_parent) if actual_parent.0 == parent_entity => {}
+        _ => return,
+    }
+    changed |= changed_transform_query.get(entity).is_ok() || rescaled.contains(&entity);
+    if !changed && !dirty.contains(&entity) {
+        return;
+    }
 
     let global_matrix = {
-        if let Ok((transform, mut global_transform)) = transform_query.get_mut(entity) {
+        if let Ok((transform, scale, mut global_transform)) = transform_query.get_unchecked(entity)
+        {
             if changed {
-                *global_transform = parent.mul_transform(*transform);
+                debug_assert_drift(transform);
+                *global_transform = with_scale::<T>(*parent * *transform, scale);
             }
             *global_transform
         } else {
@@ -63,9 +210,13 @@ fn propagate_recursive(
         for child in children.iter() {
             propagate_recursive(
                 &global_matrix,
-                changed_transform_query,
+                entity,
                 transform_query,
+                changed_transform_query,
+                parent_query,
                 children_query,
+                dirty,
+                rescaled,
                 *child,
                 changed,
             );
@@ -73,6 +224,33 @@ fn propagate_recursive(
     }
 }
 
+#[inline]
+fn with_scale<T: PropagateTransform>(global: T::Global, scale: Option<&LocalScale>) -> T::Global {
+    match scale {
+        Some(&scale) => T::apply_scale(global, scale),
+        None => global,
+    }
+}
+
+#[inline]
+fn debug_assert_drift<T: PropagateTransform>(transform: &T) {
+    debug_assert!(
+        transform.drift() < MAX_PROPAGATED_DRIFT,
+        "a transform has drifted {} from being an isometry; normalize it before propagating",
+        transform.drift()
+    );
+}
+
+/// Rounds each changed [`DGlobalTransform`] into the entity's [`GlobalTransform`],
+/// so that it can be rendered.
+pub fn dglobal_transform_sync_system(
+    mut query: Query<(&DGlobalTransform, &mut GlobalTransform), Changed<DGlobalTransform>>,
+) {
+    for (dglobal_transform, mut global_transform) in query.iter_mut() {
+        *global_transform = dglobal_transform.as_f32();
+    }
+}
+
 #[cfg(test)]
 mod test {
     use bevy_ecs::{
@@ -80,7 +258,7 @@ mod test {
         system::{CommandQueue, Commands, IntoSystem},
         world::World,
     };
-    use bevy_math::Vec3;
+    use bevy_math::{DVec3, Vec3};
 
     use super::*;
     use crate::hierarchy::{parent_update_system, BuildChildren, BuildWorldChildren};
@@ -91,7 +269,7 @@ mod test {
 
         let mut update_stage = SystemStage::parallel();
         update_stage.add_system(parent_update_system.system());
-        update_stage.add_system(transform_propagate_system.system());
+        update_stage.add_system(transform_propagate_system::<Transform>.system());
 
         let mut schedule = Schedule::default();
         schedule.add_stage("update", update_stage);
@@ -148,7 +326,7 @@ mod test {
 
         let mut update_stage = SystemStage::parallel();
         update_stage.add_system(parent_update_system.system());
-        update_stage.add_system(transform_propagate_system.system());
+        update_stage.add_system(transform_propagate_system::<Transform>.system());
 
         let mut schedule = Schedule::default();
         schedule.add_stage("update", update_stage);
@@ -195,4 +373,312 @@ mod test {
                 * Transform::from_translation(Vec3::new(0.0, 0.0, 3.0))
         );
     }
+
+    #[test]
+    fn did_propagate_dtransform() {
+        let mut world = World::default();
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(parent_update_system.system());
+        update_stage.add_system(transform_propagate_system::<DTransform>.system());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", update_stage);
+
+        let mut child = None;
+        world
+            .spawn()
+            .insert_bundle((
+                DTransform::from_translation(DVec3::new(1.0, 0.0, 0.0)),
+                DGlobalTransform::identity(),
+            ))
+            .with_children(|parent| {
+                child = Some(
+                    parent
+                        .spawn_bundle((
+                            DTransform::from_translation(DVec3::new(0.0, 2.0, 0.0)),
+                            DGlobalTransform::identity(),
+                        ))
+                        .id(),
+                );
+            });
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<DGlobalTransform>(child.unwrap()).unwrap(),
+            DGlobalTransform::from(DTransform::from_translation(DVec3::new(1.0, 0.0, 0.0)))
+                * DTransform::from_translation(DVec3::new(0.0, 2.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn did_propagate_hyperbolic_transform() {
+        let mut world = World::default();
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(parent_update_system.system());
+        update_stage.add_system(transform_propagate_system::<HyperbolicTransform>.system());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", update_stage);
+
+        let mut child = None;
+        world
+            .spawn()
+            .insert_bundle((
+                HyperbolicTransform::from_translation(Vec3::new(1.0, 0.0, 0.0)),
+                HyperbolicGlobalTransform::identity(),
+            ))
+            .with_children(|parent| {
+                child = Some(
+                    parent
+                        .spawn_bundle((
+                            HyperbolicTransform::from_translation(Vec3::new(0.0, 2.0, 0.0)),
+                            HyperbolicGlobalTransform::identity(),
+                        ))
+                        .id(),
+                );
+            });
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world
+                .get::<HyperbolicGlobalTransform>(child.unwrap())
+                .unwrap(),
+            HyperbolicGlobalTransform::from_translation(Vec3::new(1.0, 0.0, 0.0))
+                * HyperbolicTransform::from_translation(Vec3::new(0.0, 2.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn did_propagate_scale() {
+        let mut world = World::default();
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(parent_update_system.system());
+        update_stage.add_system(transform_propagate_system::<Transform>.system());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", update_stage);
+
+        let mut children = Vec::new();
+        let root = world
+            .spawn()
+            .insert_bundle((
+                Transform::from_translation(Vec3::new(1.0, 0.0, 0.0)),
+                LocalScale(2.0),
+                GlobalTransform::identity(),
+            ))
+            .with_children(|parent| {
+                children.push(
+                    parent
+                        .spawn_bundle((
+                            Transform::from_translation(Vec3::new(0.0, 0.5, 0.0)),
+                            LocalScale(3.0),
+                            GlobalTransform::identity(),
+                        ))
+                        .id(),
+                );
+                children.push(
+                    parent
+                        .spawn_bundle((
+                            Transform::from_translation(Vec3::new(0.0, 0.0, 0.5)),
+                            GlobalTransform::identity(),
+                        ))
+                        .id(),
+                );
+            })
+            .id();
+        schedule.run(&mut world);
+
+        // Scale is inherited, but does not move the children.
+        let expected = GlobalTransform::from_translation(Vec3::new(1.0, 0.0, 0.0))
+            * Transform::from_translation(Vec3::new(0.0, 0.5, 0.0));
+        let child = *world.get::<GlobalTransform>(children[0]).unwrap();
+        assert_eq!(child.biquat, expected.biquat);
+        assert_eq!(child.scale, 6.0);
+        assert_eq!(
+            world.get::<GlobalTransform>(children[1]).unwrap().scale,
+            2.0
+        );
+
+        // Changing only the scale propagates too.
+        world.get_mut::<LocalScale>(root).unwrap().0 = 0.5;
+        schedule.run(&mut world);
+        assert_eq!(
+            world.get::<GlobalTransform>(children[0]).unwrap().scale,
+            1.5
+        );
+        assert_eq!(
+            world.get::<GlobalTransform>(children[1]).unwrap().scale,
+            0.5
+        );
+
+        // So does removing it.
+        world.entity_mut(children[0]).remove::<LocalScale>();
+        schedule.run(&mut world);
+        assert_eq!(
+            world.get::<GlobalTransform>(children[0]).unwrap().scale,
+            0.5
+        );
+        world.clear_trackers();
+        world.entity_mut(root).remove::<LocalScale>();
+        schedule.run(&mut world);
+        assert_eq!(world.get::<GlobalTransform>(root).unwrap().scale, 1.0);
+        assert_eq!(
+            world.get::<GlobalTransform>(children[0]).unwrap().scale,
+            1.0
+        );
+    }
+
+    #[test]
+    fn did_propagate_dtransform_scale() {
+        let mut world = World::default();
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(parent_update_system.system());
+        update_stage.add_system(transform_propagate_system::<DTransform>.system());
+        let mut sync_stage = SystemStage::parallel();
+        sync_stage.add_system(dglobal_transform_sync_system.system());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", update_stage);
+        schedule.add_stage("sync", sync_stage);
+
+        let mut child = None;
+        let root = world
+            .spawn()
+            .insert_bundle((
+                DTransform::from_translation(DVec3::new(1.0, 0.0, 0.0)),
+                LocalScale(2.0),
+                DGlobalTransform::identity(),
+                GlobalTransform::identity(),
+            ))
+            .with_children(|parent| {
+                child = Some(
+                    parent
+                        .spawn_bundle((
+                            DTransform::from_translation(DVec3::new(0.0, 0.5, 0.0)),
+                            LocalScale(3.0),
+                            DGlobalTransform::identity(),
+                            GlobalTransform::identity(),
+                        ))
+                        .id(),
+                );
+            })
+            .id();
+        let child = child.unwrap();
+        schedule.run(&mut world);
+
+        // The scale reaches the rendered GlobalTransform too.
+        assert_eq!(world.get::<DGlobalTransform>(child).unwrap().scale, 6.0);
+        assert_eq!(world.get::<GlobalTransform>(child).unwrap().scale, 6.0);
+        assert_eq!(world.get::<GlobalTransform>(root).unwrap().scale, 2.0);
+
+        world.get_mut::<LocalScale>(root).unwrap().0 = 0.5;
+        schedule.run(&mut world);
+        assert_eq!(world.get::<GlobalTransform>(child).unwrap().scale, 1.5);
+    }
+
+    /// The global transforms of `entity` and its descendants, computed one at a time.
+    fn expected_globals(
+        world: &World,
+        entity: Entity,
+        parent: Option<GlobalTransform>,
+        globals: &mut Vec<(Entity, GlobalTransform)>,
+    ) {
+        let transform = *world.get::<Transform>(entity).unwrap();
+        let mut global = match parent {
+            Some(parent) => parent * transform,
+            None => GlobalTransform::from(transform),
+        };
+        if let Some(scale) = world.get::<LocalScale>(entity) {
+            global.scale *= scale.0;
+        }
+        globals.push((entity, global));
+        if let Some(children) = world.get::<Children>(entity) {
+            for &child in children.iter() {
+                expected_globals(world, child, Some(global), globals);
+            }
+        }
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let mut world = World::default();
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(parent_update_system.system());
+        update_stage.add_system(transform_propagate_system::<Transform>.system());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", update_stage);
+
+        // Many roots, each with a chain of nested parents and a few leaves on each.
+        let translation = |i: usize| {
+            let i = i as f32;
+            Vec3::new((i * 0.37).sin(), (i * 0.61).cos(), (i * 0.23).sin()) * 0.5
+        };
+        let mut roots = Vec::new();
+        let mut deep = Vec::new();
+        for i in 0..200 {
+            let root = world
+                .spawn()
+                .insert_bundle((
+                    Transform::from_translation(translation(i)),
+                    GlobalTransform::identity(),
+                ))
+                .id();
+            let mut parent = root;
+            for depth in 0..4 {
+                let mut chain = None;
+                world.entity_mut(parent).with_children(|builder| {
+                    for leaf in 0..3 {
+                        let id = builder
+                            .spawn_bundle((
+                                Transform::from_translation(translation(i + depth * 7 + leaf)),
+                                LocalScale(1. + leaf as f32),
+                                GlobalTransform::identity(),
+                            ))
+                            .id();
+                        chain.get_or_insert(id);
+                    }
+                });
+                parent = chain.unwrap();
+            }
+            roots.push(root);
+            deep.push(parent);
+        }
+        schedule.run(&mut world);
+
+        let mut globals = Vec::new();
+        for &root in &roots {
+            expected_globals(&world, root, None, &mut globals);
+        }
+        assert_eq!(globals.len(), 200 * 13);
+        for &(entity, global) in &globals {
+            assert_eq!(*world.get::<GlobalTransform>(entity).unwrap(), global);
+        }
+
+        // Change one deep entity and one root scale.
+        // Untouched subtrees are not written, so a stale value there stays stale.
+        *world.get_mut::<Transform>(deep[10]).unwrap() =
+            Transform::from_translation(Vec3::new(0.0, -0.3, 0.1));
+        world.entity_mut(roots[20]).insert(LocalScale(0.5));
+        let stale = GlobalTransform::from_translation(Vec3::X);
+        *world.get_mut::<GlobalTransform>(deep[30]).unwrap() = stale;
+        schedule.run(&mut world);
+
+        let mut globals = Vec::new();
+        for &root in &roots {
+            expected_globals(&world, root, None, &mut globals);
+        }
+        for &(entity, global) in &globals {
+            if entity == deep[30] {
+                assert_eq!(*world.get::<GlobalTransform>(entity).unwrap(), stale);
+            } else {
+                assert_eq!(*world.get::<GlobalTransform>(entity).unwrap(), global);
+            }
+        }
+    }
 }
@@ -0,0 +1,37 @@
+/// The radius of the spherical universe, in world units.
+///
+/// By default this is 1, so that [`Transform::from_translation`](crate::components::Transform::from_translation)
+/// measures distances in radians. That is roughly the size of Bevy's default objects,
+/// so scenes authored in meters should use a larger radius, such as 100.
+///
+/// Insert this resource before adding [`TransformPlugin`](crate::TransformPlugin) to change it.
+/// The transforms themselves are always stored for the unit sphere;
+/// use the `_in` methods, such as
+/// [`Transform::from_translation_in`](crate::components::Transform::from_translation_in),
+/// to convert from world units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UniverseRadius(pub f32);
+
+impl Default for UniverseRadius {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+/// Which space form of positive curvature the universe is.
+///
+/// `RenderPlugin` inserts this resource from the [`Geometry`](crate::space::Geometry) it draws,
+/// [`Spherical`](crate::space::Spherical) or [`Elliptic`](crate::space::Elliptic).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpaceForm {
+    /// The 3-sphere S³. Every point has a distinct antipode.
+    #[default]
+    Spherical,
+    /// Elliptic space RP³, the 3-sphere with antipodal points identified.
+    /// A [`GlobalTransform`](crate::components::GlobalTransform) and its negation are the same transform,
+    /// and geodesics close up after a distance of π instead of 2π.
+    ///
+    /// Use [`GlobalTransform::elliptic_position`](crate::components::GlobalTransform::elliptic_position)
+    /// and [`Elliptic`](crate::space::Elliptic) to measure positions and distances.
+    Elliptic,
+}